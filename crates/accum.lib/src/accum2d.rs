@@ -0,0 +1,167 @@
+use access_range::IntoAccessRange;
+use commutative_ring::CommutativeRing;
+use commutative_ring_as_additive_group::quick_group_by_add;
+use group::{group_to_quick, Group, QuickGroup};
+
+/// # 二次元累積
+///
+/// `fold(x_range, y_range)` で長方形の畳み込みを $O(1)$ で求める。
+/// 群は可換でなければならない。
+pub struct Accumulated2d<T: Clone, TFolded, TIntoFolded, Op, Inv, Id>
+where
+    Op: Fn(&T, &T) -> T,
+    Inv: Fn(&T) -> T,
+    Id: Fn() -> T,
+    TIntoFolded: Fn(T) -> TFolded,
+{
+    /// accum[x][y] は [0, x) × [0, y) の畳み込み
+    accum: Vec<Vec<T>>,
+    group: QuickGroup<T, Op, Inv, Id>,
+    t_into_folded: TIntoFolded,
+}
+
+impl<T: Clone, TFolded, TIntoFolded, Op, Inv, Id>
+    Accumulated2d<T, TFolded, TIntoFolded, Op, Inv, Id>
+where
+    Op: Fn(&T, &T) -> T,
+    Inv: Fn(&T) -> T,
+    Id: Fn() -> T,
+    TIntoFolded: Fn(T) -> TFolded,
+{
+    #[inline]
+    pub fn set_value_folded<TFolded2>(
+        self,
+        value_folded: impl Fn(T) -> TFolded2,
+    ) -> Accumulated2d<T, TFolded2, impl Fn(T) -> TFolded2, Op, Inv, Id> {
+        Accumulated2d {
+            accum: self.accum,
+            group: self.group,
+            t_into_folded: value_folded,
+        }
+    }
+
+    #[inline]
+    pub fn map_value_folded<TFolded2>(
+        self,
+        map_fn: impl Fn(TFolded) -> TFolded2,
+    ) -> Accumulated2d<T, TFolded2, impl Fn(T) -> TFolded2, Op, Inv, Id> {
+        Accumulated2d {
+            accum: self.accum,
+            group: self.group,
+            t_into_folded: move |x| map_fn((self.t_into_folded)(x)),
+        }
+    }
+
+    /// 一つ目の添字の長さ
+    #[inline]
+    pub fn len_x(&self) -> usize {
+        self.accum.len() - 1
+    }
+
+    /// 二つ目の添字の長さ
+    #[inline]
+    pub fn len_y(&self) -> usize {
+        self.accum[0].len() - 1
+    }
+
+    pub fn fold(
+        &self,
+        x_range: impl IntoAccessRange<usize>,
+        y_range: impl IntoAccessRange<usize>,
+    ) -> TFolded {
+        let x_range = x_range.into_access_range().into_range(self.len_x());
+        let y_range = y_range.into_access_range().into_range(self.len_y());
+        (self.t_into_folded)({
+            if x_range.start >= x_range.end || y_range.start >= y_range.end {
+                self.group.id()
+            } else {
+                let (x1, x2) = (x_range.start, x_range.end);
+                let (y1, y2) = (y_range.start, y_range.end);
+                let g = &self.group;
+                g.op(
+                    &g.op(&self.accum[x2][y2], &self.accum[x1][y1]),
+                    &g.inv(&g.op(&self.accum[x1][y2], &self.accum[x2][y1])),
+                )
+            }
+        })
+    }
+}
+
+fn accum2d_quick<T, Op, Inv, Id>(
+    v: Vec<Vec<T>>,
+    group: QuickGroup<T, Op, Inv, Id>,
+) -> Accumulated2d<T, T, fn(T) -> T, Op, Inv, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Inv: Fn(&T) -> T,
+    Id: Fn() -> T,
+{
+    fn id_fn<T>(x: T) -> T {
+        x
+    }
+
+    let len_y = v.first().map_or(0, |row| row.len());
+    let mut accum = Vec::with_capacity(v.len() + 1);
+    accum.push(vec![group.id(); len_y + 1]);
+    for (x, row) in v.into_iter().enumerate() {
+        assert_eq!(row.len(), len_y, "accum2d: rows must have the same length");
+        let mut next = Vec::with_capacity(len_y + 1);
+        next.push(group.id());
+        let mut row_folded = group.id();
+        for (y, e) in row.into_iter().enumerate() {
+            row_folded = group.op(&row_folded, &e);
+            next.push(group.op(&accum[x][y + 1], &row_folded));
+        }
+        accum.push(next);
+    }
+    Accumulated2d {
+        accum,
+        group,
+        t_into_folded: id_fn as fn(T) -> T,
+    }
+}
+
+/// ## 例
+/// ```
+/// use accum::accum2d_by_add;
+/// let a = accum2d_by_add(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+/// assert_eq!(a.fold(.., ..), 21);
+/// assert_eq!(a.fold(1, 1..), 11);
+/// assert_eq!(a.fold(.., 2), 9);
+/// assert_eq!(a.fold(0..1, 1..1), 0);
+/// ```
+#[inline]
+pub fn accum2d_by_add<T>(
+    v: Vec<Vec<T>>,
+) -> Accumulated2d<T, T, impl Fn(T) -> T, impl Fn(&T, &T) -> T, impl Fn(&T) -> T, impl Fn() -> T>
+where
+    T: CommutativeRing,
+{
+    accum2d_quick(v, quick_group_by_add())
+}
+
+#[inline]
+pub fn accum2d<T>(
+    v: Vec<Vec<T>>,
+) -> Accumulated2d<T, T, impl Fn(T) -> T, impl Fn(&T, &T) -> T, impl Fn(&T) -> T, impl Fn() -> T>
+where
+    T: Clone + Group,
+{
+    accum2d_quick(v, group_to_quick())
+}
+
+pub fn accum2d_by<T, Op, Inv, Id>(
+    v: Vec<Vec<T>>,
+    op: Op,
+    inv: Inv,
+    id: Id,
+) -> Accumulated2d<T, T, impl Fn(T) -> T, Op, Inv, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Inv: Fn(&T) -> T,
+    Id: Fn() -> T,
+{
+    accum2d_quick(v, QuickGroup::new(op, inv, id))
+}
@@ -0,0 +1,151 @@
+use access_range::IntoAccessRange;
+use commutative_ring::CommutativeRing;
+use commutative_ring_as_additive_group::quick_group_by_add;
+use group::{group_to_quick, Group, QuickGroup};
+
+/// # 二次元いもす法
+///
+/// 長方形への加算を $O(1)$ で記録し、 `build` で $O(HW)$ で各点の値を求める。
+/// 群は可換でなければならない。
+pub struct Imos2d<T: Clone, Op, Inv, Id>
+where
+    Op: Fn(&T, &T) -> T,
+    Inv: Fn(&T) -> T,
+    Id: Fn() -> T,
+{
+    /// (len_x + 1) × (len_y + 1) の差分
+    diff: Vec<Vec<T>>,
+    group: QuickGroup<T, Op, Inv, Id>,
+}
+
+impl<T: Clone, Op, Inv, Id> Imos2d<T, Op, Inv, Id>
+where
+    Op: Fn(&T, &T) -> T,
+    Inv: Fn(&T) -> T,
+    Id: Fn() -> T,
+{
+    #[inline]
+    pub fn len_x(&self) -> usize {
+        self.diff.len() - 1
+    }
+
+    #[inline]
+    pub fn len_y(&self) -> usize {
+        self.diff[0].len() - 1
+    }
+
+    /// # 長方形への加算
+    ///
+    /// ## 計算量
+    ///
+    /// $O(1)$
+    pub fn add(
+        &mut self,
+        x_range: impl IntoAccessRange<usize>,
+        y_range: impl IntoAccessRange<usize>,
+        value: &T,
+    ) {
+        let x_range = x_range.into_access_range().into_range(self.len_x());
+        let y_range = y_range.into_access_range().into_range(self.len_y());
+        if x_range.start >= x_range.end || y_range.start >= y_range.end {
+            return;
+        }
+        let inv = self.group.inv(value);
+        for (x, y, v) in [
+            (x_range.start, y_range.start, value),
+            (x_range.start, y_range.end, &inv),
+            (x_range.end, y_range.start, &inv),
+            (x_range.end, y_range.end, value),
+        ] {
+            self.diff[x][y] = self.group.op(&self.diff[x][y], v);
+        }
+    }
+
+    /// # 各点の値の計算
+    ///
+    /// ## 計算量
+    ///
+    /// $O(HW)$
+    pub fn build(self) -> Vec<Vec<T>> {
+        let len_x = self.len_x();
+        let len_y = self.len_y();
+        let group = self.group;
+        let mut res: Vec<Vec<T>> = Vec::with_capacity(len_x);
+        for (x, mut row) in self.diff.into_iter().take(len_x).enumerate() {
+            row.truncate(len_y);
+            for y in 1..len_y {
+                row[y] = group.op(&row[y - 1], &row[y]);
+            }
+            if x > 0 {
+                for y in 0..len_y {
+                    row[y] = group.op(&res[x - 1][y], &row[y]);
+                }
+            }
+            res.push(row);
+        }
+        res
+    }
+}
+
+fn imos2d_quick<T, Op, Inv, Id>(
+    len_x: usize,
+    len_y: usize,
+    group: QuickGroup<T, Op, Inv, Id>,
+) -> Imos2d<T, Op, Inv, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Inv: Fn(&T) -> T,
+    Id: Fn() -> T,
+{
+    Imos2d {
+        diff: vec![vec![group.id(); len_y + 1]; len_x + 1],
+        group,
+    }
+}
+
+/// ## 例
+/// ```
+/// use accum::imos2d_by_add;
+/// let mut imos = imos2d_by_add(2, 3);
+/// imos.add(.., 1.., &1);
+/// imos.add(1, .., &10);
+/// assert_eq!(imos.build(), vec![vec![0, 1, 1], vec![10, 11, 11]]);
+/// ```
+#[inline]
+pub fn imos2d_by_add<T>(
+    len_x: usize,
+    len_y: usize,
+) -> Imos2d<T, impl Fn(&T, &T) -> T, impl Fn(&T) -> T, impl Fn() -> T>
+where
+    T: CommutativeRing,
+{
+    imos2d_quick(len_x, len_y, quick_group_by_add())
+}
+
+#[inline]
+pub fn imos2d<T>(
+    len_x: usize,
+    len_y: usize,
+) -> Imos2d<T, impl Fn(&T, &T) -> T, impl Fn(&T) -> T, impl Fn() -> T>
+where
+    T: Clone + Group,
+{
+    imos2d_quick(len_x, len_y, group_to_quick())
+}
+
+pub fn imos2d_by<T, Op, Inv, Id>(
+    len_x: usize,
+    len_y: usize,
+    op: Op,
+    inv: Inv,
+    id: Id,
+) -> Imos2d<T, Op, Inv, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Inv: Fn(&T) -> T,
+    Id: Fn() -> T,
+{
+    imos2d_quick(len_x, len_y, QuickGroup::new(op, inv, id))
+}
@@ -3,6 +3,12 @@ use commutative_ring::CommutativeRing;
 use commutative_ring_as_additive_group::quick_group_by_add;
 use group::{group_to_quick, Group, QuickGroup};
 
+mod accum2d;
+mod imos2d;
+
+pub use accum2d::*;
+pub use imos2d::*;
+
 pub struct Accumulated<T: Clone, TFolded, TIntoFolded, Op, Inv, Id>
 where
     Op: Fn(&T, &T) -> T,
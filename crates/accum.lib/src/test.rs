@@ -1,5 +1,5 @@
 #![allow(clippy::reversed_empty_ranges)]
-use crate::{accum_by, accum_by_add};
+use crate::{accum2d_by, accum2d_by_add, accum_by, accum_by_add, imos2d_by_add};
 
 #[test]
 fn test_accum() {
@@ -52,3 +52,60 @@ fn test_accum_by() {
     assert_eq!(a.fold(0..=3), (-3, 21));
     assert_eq!(a.fold(0..0), (0, 0));
 }
+
+#[test]
+fn test_accum2d_all_ranges() {
+    let v = (0..4)
+        .map(|x| {
+            (0..5)
+                .map(|y| (x * 7 + y * 3) % 11 - 5)
+                .collect::<Vec<i64>>()
+        })
+        .collect::<Vec<_>>();
+    let a = accum2d_by_add(v.clone());
+    assert_eq!((a.len_x(), a.len_y()), (4, 5));
+    for x1 in 0..=4 {
+        for x2 in x1..=4 {
+            for y1 in 0..=5 {
+                for y2 in y1..=5 {
+                    let naive = (x1..x2)
+                        .flat_map(|x| (y1..y2).map(move |y| (x, y)))
+                        .map(|(x, y)| v[x][y])
+                        .sum::<i64>();
+                    assert_eq!(a.fold(x1..x2, y1..y2), naive);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_accum2d_by_xor() {
+    let v = vec![vec![1_u32, 2], vec![4, 8], vec![16, 32]];
+    let a = accum2d_by(v, |a, b| a ^ b, |a| *a, || 0);
+    assert_eq!(a.fold(.., ..), 63);
+    assert_eq!(a.fold(1.., 1), 40);
+    assert_eq!(a.fold(..2, ..), 15);
+    assert_eq!(a.fold(2..2, ..), 0);
+}
+
+#[test]
+fn test_imos2d() {
+    let rects = [
+        (0..2, 1..3, 5),
+        (1..4, 0..5, -2),
+        (3..4, 4..5, 7),
+        (0..4, 2..2, 100),
+    ];
+    let mut imos = imos2d_by_add(4, 5);
+    let mut naive = vec![vec![0_i64; 5]; 4];
+    for (xr, yr, v) in rects.iter().cloned() {
+        imos.add(xr.clone(), yr.clone(), &v);
+        for row in &mut naive[xr] {
+            for e in &mut row[yr.clone()] {
+                *e += v;
+            }
+        }
+    }
+    assert_eq!(imos.build(), naive);
+}
@@ -1,3 +1,5 @@
 mod quick;
+mod r#trait;
 
 pub use quick::*;
+pub use r#trait::*;
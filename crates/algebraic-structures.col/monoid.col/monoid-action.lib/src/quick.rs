@@ -1,3 +1,5 @@
+use monoid::QuickMonoid;
+
 /// # モノイドアクション
 #[doc = include_str!("../trait_description.md")]
 #[derive(Clone, Copy)]
@@ -62,3 +64,42 @@ pub type QuickMonoidActionStatic<T, A> = QuickMonoidAction<
     fn() -> A,
     fn(&A, &T) -> T,
 >;
+
+/// # 作用が自明なモノイドアクションの型
+///
+/// 作用の型を `()` とし、作用させても値を変えない。
+/// `QuickMonoid` から `From` で変換できる。
+///
+/// ```
+/// use monoid::QuickMonoid;
+/// use monoid_action::QuickMonoidActionTrivial;
+/// let q: QuickMonoidActionTrivial<i32, _, _> =
+///     QuickMonoid::new(|a: &i32, b: &i32| a + b, || 0).into();
+/// assert_eq!(q.act_app(&q.act_id(), &q.op(&1, &2)), 3);
+/// ```
+pub type QuickMonoidActionTrivial<T, Op, Id> =
+    QuickMonoidAction<T, (), Op, Id, fn(&(), &()), fn(), fn(&(), &T) -> T>;
+
+fn trivial_act_op(_: &(), _: &()) {}
+fn trivial_act_id() {}
+fn trivial_act_app<T: Clone>(_: &(), x: &T) -> T {
+    x.clone()
+}
+
+impl<T, Op, Id> From<QuickMonoid<T, Op, Id>> for QuickMonoidActionTrivial<T, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+{
+    fn from(monoid: QuickMonoid<T, Op, Id>) -> Self {
+        let (op, id) = monoid.into_parts();
+        QuickMonoidAction::new(
+            op,
+            id,
+            trivial_act_op as fn(&(), &()),
+            trivial_act_id as fn(),
+            trivial_act_app as fn(&(), &T) -> T,
+        )
+    }
+}
@@ -0,0 +1,69 @@
+use super::{QuickMonoidAction, QuickMonoidActionStatic};
+use monoid::Monoid;
+
+/// # モノイドアクション
+///
+/// 型 `T` のモノイドに対して、型 `A` のモノイドが `act_app` によって作用する。
+/// `Self` は作用の組を表すための型であり、値を持つ必要はない。
+#[doc = include_str!("../trait_description.md")]
+///
+/// ## 例
+///
+/// ```
+/// use monoid::Monoid;
+/// use monoid_action::MonoidAction;
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Max(i64);
+/// impl Monoid for Max {
+///     fn op(&self, other: &Self) -> Self {
+///         Max(self.0.max(other.0))
+///     }
+///     fn id() -> Self {
+///         Max(i64::MIN)
+///     }
+/// }
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Add(i64);
+/// impl Monoid for Add {
+///     fn op(&self, other: &Self) -> Self {
+///         Add(self.0 + other.0)
+///     }
+///     fn id() -> Self {
+///         Add(0)
+///     }
+/// }
+///
+/// struct AddMax;
+/// impl MonoidAction for AddMax {
+///     type T = Max;
+///     type A = Add;
+///     fn act_app(a: &Add, x: &Max) -> Max {
+///         Max(x.0.saturating_add(a.0))
+///     }
+/// }
+///
+/// let q = AddMax::as_quick();
+/// assert_eq!(q.act_app(&Add(3), &q.op(&Max(1), &Max(4))), Max(7));
+/// assert_eq!(q.act_app(&q.act_id(), &q.id()), Max(i64::MIN));
+/// ```
+pub trait MonoidAction {
+    type T: Monoid;
+    type A: Monoid;
+
+    fn act_app(a: &Self::A, x: &Self::T) -> Self::T;
+
+    fn as_quick() -> QuickMonoidActionStatic<Self::T, Self::A>
+    where
+        Self: Sized,
+    {
+        QuickMonoidAction::new(
+            <Self::T as Monoid>::op as fn(&Self::T, &Self::T) -> Self::T,
+            <Self::T as Monoid>::id as fn() -> Self::T,
+            <Self::A as Monoid>::op as fn(&Self::A, &Self::A) -> Self::A,
+            <Self::A as Monoid>::id as fn() -> Self::A,
+            <Self as MonoidAction>::act_app as fn(&Self::A, &Self::T) -> Self::T,
+        )
+    }
+}
@@ -21,6 +21,10 @@ where
     pub fn id(&self) -> T {
         (self.id)()
     }
+    /// 演算子と単位元に分解する
+    pub fn into_parts(self) -> (Op, Id) {
+        (self.op, self.id)
+    }
 }
 
 pub type QuickMonoidStatic<T> = QuickMonoid<T, for<'a, 'b> fn(&'a T, &'b T) -> T, fn() -> T>;
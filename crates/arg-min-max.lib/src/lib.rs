@@ -0,0 +1,45 @@
+use std::cmp;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArgMin<T, Index> {
+    pub min: T,
+    pub index: Index,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArgMax<T, Index> {
+    pub max: T,
+    pub index: Index,
+}
+
+/// 最小値・最大値を取る位置が複数あるときに、どれを選ぶか
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ArgPosition {
+    /// 最も左の位置
+    #[default]
+    Leftmost,
+    /// 最も右の位置
+    Rightmost,
+}
+
+impl ArgPosition {
+    /// 値が等しい 2 つの位置 `a`, `b` から選ぶ
+    ///
+    /// `usize::MAX` は単位元の番兵として扱い、選ばれない。
+    ///
+    /// ```
+    /// use arg_min_max::ArgPosition;
+    /// assert_eq!(ArgPosition::Leftmost.select(3, 5), 3);
+    /// assert_eq!(ArgPosition::Rightmost.select(3, 5), 5);
+    /// assert_eq!(ArgPosition::Rightmost.select(3, usize::MAX), 3);
+    /// assert_eq!(ArgPosition::Leftmost.select(usize::MAX, 5), 5);
+    /// ```
+    #[inline]
+    pub fn select(self, a: usize, b: usize) -> usize {
+        match self {
+            ArgPosition::Leftmost => a.min(b),
+            // 番兵 usize::MAX を 0 として比較する
+            ArgPosition::Rightmost => cmp::max_by_key(a, b, |i| i.wrapping_add(1)),
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use access_range::IntoAccessRange;
+use ceil_log2::ceil_log2_usize;
+use monoid::{Monoid, QuickMonoid};
+
+/// # Disjoint Sparse Table
+///
+/// 任意のモノイドについて、 $O(1)$ で区間の畳み込みを行う。
+/// 冪等性や逆元を必要とせず、可換でなくてもよい。値の更新はできない。
+pub struct DisjointSparseTable<T, TFolded, TIntoFolded, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+    TIntoFolded: Fn(T) -> TFolded,
+{
+    vec: Vec<T>,
+    /// 高さ `k` の段では、長さ `2^(k+1)` のブロックの中央 `m` について、
+    /// 左半分の `i` に `i..m` の畳み込みを、右半分の `i` に `m..=i` の畳み込みを持つ
+    table: Vec<Vec<T>>,
+    monoid: QuickMonoid<T, Op, Id>,
+    t_into_folded: TIntoFolded,
+}
+
+/// # 名前を付けられる Disjoint Sparse Table の型
+///
+/// 関数をすべて `fn` ポインタとして持つ `DisjointSparseTable` 。
+pub type DisjointSparseTableStatic<T, TFolded = T> =
+    DisjointSparseTable<T, TFolded, fn(T) -> TFolded, fn(&T, &T) -> T, fn() -> T>;
+
+impl<T, TFolded, TIntoFolded, Op, Id> DisjointSparseTable<T, TFolded, TIntoFolded, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+    TIntoFolded: Fn(T) -> TFolded,
+{
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.vec.len()
+    }
+
+    #[inline]
+    pub fn set_value_folded<TFolded2, TIntoFolded2>(
+        self,
+        value_folded: TIntoFolded2,
+    ) -> DisjointSparseTable<T, TFolded2, TIntoFolded2, Op, Id>
+    where
+        TIntoFolded2: Fn(T) -> TFolded2,
+    {
+        DisjointSparseTable {
+            vec: self.vec,
+            table: self.table,
+            monoid: self.monoid,
+            t_into_folded: value_folded,
+        }
+    }
+
+    /// # 区間の畳み込み
+    ///
+    /// ## 計算量
+    ///
+    /// $O(1)$
+    pub fn fold(&self, range: impl IntoAccessRange<usize>) -> TFolded {
+        let range = range.into_access_range().into_range(self.size());
+        (self.t_into_folded)({
+            if range.start >= range.end {
+                self.monoid.id()
+            } else {
+                let l = range.start;
+                let r = range.end - 1;
+                if l == r {
+                    self.vec[l].clone()
+                } else {
+                    let k = (usize::BITS - 1 - (l ^ r).leading_zeros()) as usize;
+                    let row = &self.table[k];
+                    self.monoid.op(&row[l], &row[r])
+                }
+            }
+        })
+    }
+}
+
+/// # Disjoint Sparse Table の構築 (`QuickMonoid` による指定)
+///
+/// ## 計算量
+///
+/// $O(N \log N)$
+pub fn disjoint_sparse_table_new_quick_monoid<T, Op, Id>(
+    vec: Vec<T>,
+    monoid: QuickMonoid<T, Op, Id>,
+) -> DisjointSparseTable<T, T, fn(T) -> T, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+{
+    fn id_fn<T>(x: T) -> T {
+        x
+    }
+
+    let n = vec.len();
+    let height = ceil_log2_usize(n);
+    let mut table = Vec::with_capacity(height);
+    for k in 0..height {
+        let half = 1 << k;
+        let mut row = vec.clone();
+        for m in (half..n).step_by(half * 2) {
+            let mut acc = vec[m - 1].clone();
+            for i in (m - half..m - 1).rev() {
+                acc = monoid.op(&vec[i], &acc);
+                row[i] = acc.clone();
+            }
+            let mut acc = vec[m].clone();
+            for i in m + 1..(m + half).min(n) {
+                acc = monoid.op(&acc, &vec[i]);
+                row[i] = acc.clone();
+            }
+        }
+        table.push(row);
+    }
+    DisjointSparseTable {
+        vec,
+        table,
+        monoid,
+        t_into_folded: id_fn as fn(T) -> T,
+    }
+}
+
+/// # Disjoint Sparse Table の構築 (直接指定)
+///
+/// ## 計算量
+///
+/// $O(N \log N)$
+///
+/// ## 例
+/// ```
+/// use disjoint_sparse_table::disjoint_sparse_table_new;
+/// // アフィン変換 x -> a * x + b の合成 (左から順に適用する)
+/// let dst = disjoint_sparse_table_new(
+///     vec![(2, 1), (3, 0), (1, 5), (2, 2)],
+///     |f: &(i64, i64), g: &(i64, i64)| (f.0 * g.0, f.1 * g.0 + g.1),
+///     || (1, 0),
+/// )
+/// .set_value_folded(|f| f.0 * 10 + f.1);
+/// assert_eq!(dst.fold(..), 12 * 10 + 18);
+/// assert_eq!(dst.fold(1..3), 3 * 10 + 5);
+/// assert_eq!(dst.fold(2..2), 10);
+/// ```
+#[inline]
+pub fn disjoint_sparse_table_new<T, Op, Id>(
+    vec: Vec<T>,
+    op: Op,
+    id: Id,
+) -> DisjointSparseTable<T, T, fn(T) -> T, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+{
+    disjoint_sparse_table_new_quick_monoid(vec, QuickMonoid::new(op, id))
+}
+
+/// # Disjoint Sparse Table の構築 (`Monoid` による指定)
+///
+/// ## 計算量
+///
+/// $O(N \log N)$
+#[inline]
+pub fn disjoint_sparse_table_new_monoid<T>(vec: Vec<T>) -> DisjointSparseTableStatic<T>
+where
+    T: Clone + Monoid,
+{
+    disjoint_sparse_table_new_quick_monoid(vec, T::as_quick())
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,51 @@
+use super::*;
+use rand::{self, Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+#[test]
+fn test_concat_random() {
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    for n in 0..=70 {
+        let v = (0..n)
+            .map(|_| ((b'a' + rng.gen_range(0..26)) as char).to_string())
+            .collect::<Vec<String>>();
+        let dst = disjoint_sparse_table_new(
+            v.clone(),
+            |a: &String, b: &String| format!("{}{}", a, b),
+            String::new,
+        );
+        assert_eq!(dst.size(), n);
+        for l in 0..=n {
+            for r in l..=n {
+                assert_eq!(dst.fold(l..r), v[l..r].concat());
+            }
+        }
+    }
+}
+
+#[test]
+fn test_static_monoid() {
+    #[derive(Clone, Debug, PartialEq)]
+    struct Concat(String);
+    impl Monoid for Concat {
+        fn op(&self, other: &Self) -> Self {
+            Concat(format!("{}{}", self.0, other.0))
+        }
+        fn id() -> Self {
+            Concat(String::new())
+        }
+    }
+    struct S {
+        dst: DisjointSparseTableStatic<Concat>,
+    }
+    let s = S {
+        dst: disjoint_sparse_table_new_monoid(
+            ["x", "y", "z"]
+                .iter()
+                .map(|s| Concat(s.to_string()))
+                .collect(),
+        ),
+    };
+    assert_eq!(s.dst.fold(..), Concat("xyz".to_owned()));
+    assert_eq!(s.dst.fold(1..), Concat("yz".to_owned()));
+}
@@ -0,0 +1,463 @@
+use access_range::IntoAccessRange;
+use ceil_log2::ceil_log2_usize;
+use std::marker::PhantomData;
+
+/// # 双対セグメントツリー
+///
+/// 作用素のみを木に持ち、区間への作用と一点の取得を行う。
+/// 値のモノイドを必要としないため、 `LazySegmentTree` より軽い。
+///
+/// 作用は `QuickMonoidAction` と同じ `act_op`, `act_id`, `act_app` で指定する。
+/// 作用を積む前に経路上の作用を子へ伝播するため、可換でない作用に対しても正しく動作する。
+pub struct DualSegmentTree<
+    T,
+    TGetter,
+    TSetter,
+    A,
+    ASetter,
+    TIntoGetter,
+    TFromSetter,
+    AFromSetter,
+    ActOp,
+    ActId,
+    ActApp,
+> where
+    ActOp: Fn(&A, &A) -> A,
+    ActId: Fn() -> A,
+    ActApp: Fn(&A, &T) -> T,
+
+    TIntoGetter: Fn(T, /* index */ usize) -> TGetter,
+    TFromSetter: Fn(TSetter, /* index */ usize) -> T,
+    AFromSetter: Fn(ASetter) -> A,
+{
+    act_op: ActOp,
+    act_id: ActId,
+    act_app: ActApp,
+
+    /// 作用される前の値
+    values: Vec<T>,
+    /// lazy.len() == 2 * size_pow2
+    lazy: Vec<A>,
+    /// 論理的な長さ
+    size: usize,
+    /// lenをそれ以上の最小の2羃に丸めたもの
+    size_pow2: usize,
+    /// size_pow2 == 1 << height
+    height: usize,
+
+    t_into_getter: TIntoGetter,
+    t_from_setter: TFromSetter,
+    a_from_setter: AFromSetter,
+    phantom: PhantomData<(TSetter, ASetter)>,
+}
+
+/// # 名前を付けられる双対セグメントツリーの型
+///
+/// 関数をすべて `fn` ポインタとして持つ `DualSegmentTree` 。
+pub type DualSegmentTreeStatic<T, TGetter = T, TSetter = T, A = T, ASetter = A> = DualSegmentTree<
+    T,
+    TGetter,
+    TSetter,
+    A,
+    ASetter,
+    fn(T, usize) -> TGetter,
+    fn(TSetter, usize) -> T,
+    fn(ASetter) -> A,
+    fn(&A, &A) -> A,
+    fn() -> A,
+    fn(&A, &T) -> T,
+>;
+
+impl<
+        T,
+        TGetter,
+        TSetter,
+        A,
+        ASetter,
+        TIntoGetter,
+        TFromSetter,
+        AFromSetter,
+        ActOp,
+        ActId,
+        ActApp,
+    >
+    DualSegmentTree<
+        T,
+        TGetter,
+        TSetter,
+        A,
+        ASetter,
+        TIntoGetter,
+        TFromSetter,
+        AFromSetter,
+        ActOp,
+        ActId,
+        ActApp,
+    >
+where
+    ActOp: Fn(&A, &A) -> A,
+    ActId: Fn() -> A,
+    ActApp: Fn(&A, &T) -> T,
+
+    TIntoGetter: Fn(T, /* index */ usize) -> TGetter,
+    TFromSetter: Fn(TSetter, /* index */ usize) -> T,
+    AFromSetter: Fn(ASetter) -> A,
+{
+    #[inline(always)]
+    fn leaf_of(&self, index: usize) -> usize {
+        debug_assert!(index <= self.size);
+        index + self.size_pow2
+    }
+
+    #[inline(always)]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn set_value_getter<TGetter2, TIntoGetter2>(
+        self,
+        fn_getter: TIntoGetter2,
+    ) -> DualSegmentTree<
+        T,
+        TGetter2,
+        TSetter,
+        A,
+        ASetter,
+        TIntoGetter2,
+        TFromSetter,
+        AFromSetter,
+        ActOp,
+        ActId,
+        ActApp,
+    >
+    where
+        TIntoGetter2: Fn(T, usize) -> TGetter2,
+    {
+        DualSegmentTree {
+            act_op: self.act_op,
+            act_id: self.act_id,
+            act_app: self.act_app,
+            values: self.values,
+            lazy: self.lazy,
+            size: self.size,
+            size_pow2: self.size_pow2,
+            height: self.height,
+
+            t_into_getter: fn_getter,
+            t_from_setter: self.t_from_setter,
+            a_from_setter: self.a_from_setter,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn set_value_setter<TSetter2, TFromSetter2>(
+        self,
+        fn_setter: TFromSetter2,
+    ) -> DualSegmentTree<
+        T,
+        TGetter,
+        TSetter2,
+        A,
+        ASetter,
+        TIntoGetter,
+        TFromSetter2,
+        AFromSetter,
+        ActOp,
+        ActId,
+        ActApp,
+    >
+    where
+        TFromSetter2: Fn(TSetter2, usize) -> T,
+    {
+        DualSegmentTree {
+            act_op: self.act_op,
+            act_id: self.act_id,
+            act_app: self.act_app,
+            values: self.values,
+            lazy: self.lazy,
+            size: self.size,
+            size_pow2: self.size_pow2,
+            height: self.height,
+
+            t_into_getter: self.t_into_getter,
+            t_from_setter: fn_setter,
+            a_from_setter: self.a_from_setter,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn set_action_setter<ASetter2, AFromSetter2>(
+        self,
+        fn_setter: AFromSetter2,
+    ) -> DualSegmentTree<
+        T,
+        TGetter,
+        TSetter,
+        A,
+        ASetter2,
+        TIntoGetter,
+        TFromSetter,
+        AFromSetter2,
+        ActOp,
+        ActId,
+        ActApp,
+    >
+    where
+        AFromSetter2: Fn(ASetter2) -> A,
+    {
+        DualSegmentTree {
+            act_op: self.act_op,
+            act_id: self.act_id,
+            act_app: self.act_app,
+            values: self.values,
+            lazy: self.lazy,
+            size: self.size,
+            size_pow2: self.size_pow2,
+            height: self.height,
+
+            t_into_getter: self.t_into_getter,
+            t_from_setter: self.t_from_setter,
+            a_from_setter: fn_setter,
+            phantom: PhantomData,
+        }
+    }
+
+    /// ノード `tree_index` の作用を子に伝播する
+    #[inline]
+    unsafe fn push_unchecked(&mut self, tree_index: usize) {
+        debug_assert!(1 <= tree_index && tree_index < self.size_pow2);
+
+        let (left_new, right_new) = {
+            let lazy_it = self.lazy.get_unchecked(tree_index);
+            let left = self.lazy.get_unchecked(2 * tree_index);
+            let right = self.lazy.get_unchecked(2 * tree_index + 1);
+            ((self.act_op)(lazy_it, left), (self.act_op)(lazy_it, right))
+        };
+        *self.lazy.get_unchecked_mut(2 * tree_index) = left_new;
+        *self.lazy.get_unchecked_mut(2 * tree_index + 1) = right_new;
+        *self.lazy.get_unchecked_mut(tree_index) = (self.act_id)();
+    }
+
+    /// 葉 `i` の祖先 (葉自身を除く) の作用を根から順に伝播する
+    #[inline]
+    unsafe fn push_down_unchecked(&mut self, i: usize) {
+        debug_assert!(i < self.size);
+        let leaf = self.leaf_of(i);
+        for h in (1..=self.height).rev() {
+            self.push_unchecked(leaf >> h);
+        }
+    }
+
+    /// # 取得
+    ///
+    /// 根から葉までの作用を合成して、元の値に作用させたものを返す。
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log N)$
+    #[inline]
+    pub fn get(&self, index: usize) -> TGetter {
+        assert!(index < self.size, "index out of range: {}", index);
+        let mut k = self.leaf_of(index);
+        let mut a = (self.act_op)(unsafe { self.lazy.get_unchecked(k) }, &(self.act_id)());
+        while k != 1 {
+            k >>= 1;
+            a = (self.act_op)(unsafe { self.lazy.get_unchecked(k) }, &a);
+        }
+        (self.t_into_getter)(
+            (self.act_app)(&a, unsafe { self.values.get_unchecked(index) }),
+            index,
+        )
+    }
+
+    /// # セット
+    ///
+    /// これまでの作用を打ち消して、値を置き換える。
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log N)$
+    #[inline]
+    pub fn set(&mut self, index: usize, v: TSetter) {
+        assert!(index < self.size, "index out of range: {}", index);
+        unsafe { self.push_down_unchecked(index) };
+        let k = self.leaf_of(index);
+        *unsafe { self.lazy.get_unchecked_mut(k) } = (self.act_id)();
+        *unsafe { self.values.get_unchecked_mut(index) } = (self.t_from_setter)(v, index);
+    }
+
+    /// # 区間への作用
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log N)$
+    #[inline]
+    pub fn act(&mut self, range: impl IntoAccessRange<usize>, a: ASetter) {
+        self.act_inner(range, (self.a_from_setter)(a));
+    }
+
+    fn act_inner(&mut self, range: impl IntoAccessRange<usize>, a: A) {
+        if self.size == 0 {
+            return;
+        }
+        let range = range.into_access_range().into_range(self.size);
+        if range.start >= range.end {
+            return;
+        }
+
+        unsafe { self.push_down_unchecked(range.start) };
+        unsafe { self.push_down_unchecked(range.end - 1) };
+
+        let mut l = self.leaf_of(range.start);
+        let mut r = self.leaf_of(range.end);
+        while l < r {
+            if l & 1 != 0 {
+                let p = unsafe { self.lazy.get_unchecked_mut(l) };
+                *p = (self.act_op)(&a, p);
+                l += 1;
+            }
+            if r & 1 != 0 {
+                r -= 1;
+                let p = unsafe { self.lazy.get_unchecked_mut(r) };
+                *p = (self.act_op)(&a, p);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+    }
+}
+
+/// # 双対セグメントツリーの構築 (直接指定)
+///
+/// 初期リスト、作用素の演算子、作用素の単位元、作用の順で指定する。
+///
+/// ## 計算量
+///
+/// $O(N)$
+///
+/// ## 例
+///
+/// ```
+/// use dual_segment_tree::dual_segment_tree_new;
+/// // アフィン変換 x -> a * x + b を区間に作用させる
+/// let mut seg = dual_segment_tree_new(
+///     vec![1, 4, 2, 3, 8],
+///     // act_op: (f . g)(x) = f(g(x))
+///     |f: &(i64, i64), g: &(i64, i64)| (f.0 * g.0, f.0 * g.1 + f.1),
+///     // act_id
+///     || (1, 0),
+///     // act_app
+///     |f, x| f.0 * x + f.1,
+/// );
+/// seg.act(1..4, (2, 1));
+/// seg.act(..2, (1, -3));
+/// assert_eq!(seg.get(0), -2);
+/// assert_eq!(seg.get(1), 6);
+/// assert_eq!(seg.get(3), 7);
+/// assert_eq!(seg.get(4), 8);
+/// seg.set(1, 0);
+/// assert_eq!(seg.get(1), 0);
+/// ```
+#[inline]
+pub fn dual_segment_tree_new<T, A, ActOp, ActId, ActApp>(
+    vec: Vec<T>,
+    act_op: ActOp,
+    act_id: ActId,
+    act_app: ActApp,
+) -> DualSegmentTree<
+    T,
+    T,
+    T,
+    A,
+    A,
+    impl Fn(T, usize) -> T,
+    impl Fn(T, usize) -> T,
+    impl Fn(A) -> A,
+    ActOp,
+    ActId,
+    ActApp,
+>
+where
+    ActOp: Fn(&A, &A) -> A,
+    ActId: Fn() -> A,
+    ActApp: Fn(&A, &T) -> T,
+{
+    dual_segment_tree_new_internal(vec, act_op, act_id, act_app)
+}
+
+/// # 双対セグメントツリーの構築 (`fn` ポインタによる指定)
+///
+/// `dual_segment_tree_new` と同じだが、名前を付けられる `DualSegmentTreeStatic` を返す。
+///
+/// ## 計算量
+///
+/// $O(N)$
+#[inline]
+pub fn dual_segment_tree_new_static<T, A>(
+    vec: Vec<T>,
+    act_op: fn(&A, &A) -> A,
+    act_id: fn() -> A,
+    act_app: fn(&A, &T) -> T,
+) -> DualSegmentTreeStatic<T, T, T, A> {
+    dual_segment_tree_new_internal(vec, act_op, act_id, act_app)
+}
+
+#[inline]
+fn dual_segment_tree_new_internal<T, A, ActOp, ActId, ActApp>(
+    vec: Vec<T>,
+    act_op: ActOp,
+    act_id: ActId,
+    act_app: ActApp,
+) -> DualSegmentTree<
+    T,
+    T,
+    T,
+    A,
+    A,
+    fn(T, usize) -> T,
+    fn(T, usize) -> T,
+    fn(A) -> A,
+    ActOp,
+    ActId,
+    ActApp,
+>
+where
+    ActOp: Fn(&A, &A) -> A,
+    ActId: Fn() -> A,
+    ActApp: Fn(&A, &T) -> T,
+{
+    fn id_fn<T>(x: T) -> T {
+        x
+    }
+    fn id_fn_idx<T>(x: T, _: usize) -> T {
+        x
+    }
+
+    let len = vec.len();
+    let height = ceil_log2_usize(len);
+    let len2 = 1 << height;
+    let mut lazy = Vec::new();
+    lazy.reserve_exact(len2 * 2);
+    for _ in 0..len2 * 2 {
+        lazy.push(act_id());
+    }
+    DualSegmentTree {
+        act_op,
+        act_id,
+        act_app,
+
+        values: vec,
+        lazy,
+        size: len,
+        size_pow2: len2,
+        height,
+
+        t_into_getter: id_fn_idx as fn(T, usize) -> T,
+        t_from_setter: id_fn_idx as fn(T, usize) -> T,
+        a_from_setter: id_fn as fn(A) -> A,
+        phantom: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,87 @@
+use super::*;
+use rand::{self, Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+const MOD: i64 = 998244353;
+
+#[test]
+fn test_affine_full() {
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    for n in 0..=40 {
+        let mut v = (0..n).map(|_| rng.gen_range(0..MOD)).collect::<Vec<i64>>();
+        let mut seg = dual_segment_tree_new(
+            v.clone(),
+            |f: &(i64, i64), g: &(i64, i64)| (f.0 * g.0 % MOD, (f.0 * g.1 + f.1) % MOD),
+            || (1, 0),
+            |f, x| (f.0 * x + f.1) % MOD,
+        );
+        assert_eq!(seg.size(), n);
+
+        if n == 0 {
+            continue;
+        }
+
+        for _ in 0..100 {
+            let i = rng.gen_range(0..n);
+            let x = rng.gen_range(0..MOD);
+            seg.set(i, x);
+            v[i] = x;
+
+            let l = rng.gen_range(0..n);
+            let r = rng.gen_range(l..=n);
+            let f = (rng.gen_range(0..MOD), rng.gen_range(0..MOD));
+            seg.act(l..r, f);
+            for e in v.iter_mut().take(r).skip(l) {
+                *e = (f.0 * *e + f.1) % MOD;
+            }
+
+            for (i, e) in v.iter().enumerate() {
+                assert_eq!(seg.get(i), *e);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_projection() {
+    let mut seg = dual_segment_tree_new(
+        vec![1_i64, 2, 3],
+        |a: &i64, b: &i64| a + b,
+        || 0,
+        |a, x| a + x,
+    )
+    .set_value_getter(|x, i| (x, i))
+    .set_value_setter(|x: i32, _| x as i64)
+    .set_action_setter(|a: i32| a as i64);
+    seg.act(1.., 10);
+    assert_eq!(seg.get(0), (1, 0));
+    assert_eq!(seg.get(2), (13, 2));
+    seg.set(2, 5);
+    assert_eq!(seg.get(2), (5, 2));
+}
+
+#[test]
+fn test_static_in_struct_field() {
+    struct S {
+        seg: DualSegmentTreeStatic<String, String, String, String>,
+    }
+    fn act_op(a: &String, b: &String) -> String {
+        format!("{}{}", a, b)
+    }
+    fn act_id() -> String {
+        String::new()
+    }
+    fn act_app(a: &String, x: &String) -> String {
+        format!("{}{}", a, x)
+    }
+    let mut s = S {
+        seg: dual_segment_tree_new_static(vec!["x".to_owned(); 4], act_op, act_id, act_app),
+    };
+    s.seg.act(..3, "a".to_owned());
+    s.seg.act(1..2, "b".to_owned());
+    s.seg.act(1.., "c".to_owned());
+    assert_eq!(s.seg.get(0), "ax");
+    assert_eq!(s.seg.get(1), "cbax");
+    assert_eq!(s.seg.get(2), "cax");
+    assert_eq!(s.seg.get(3), "cx");
+}
@@ -0,0 +1,262 @@
+use access_range::IntoAccessRange;
+use commutative_ring::CommutativeRing;
+use commutative_ring_as_additive_group::quick_group_by_add;
+use group::{group_to_quick, Group, QuickGroup};
+
+mod range_add;
+pub use range_add::*;
+
+/// # Fenwick Tree (Binary Indexed Tree)
+///
+/// 一点への加算と区間の畳み込みを $O(\log N)$ で行う。
+/// 群の演算は可換であることを想定する。
+pub struct FenwickTree<T: Clone, TFolded, TIntoFolded, Op, Inv, Id>
+where
+    Op: Fn(&T, &T) -> T,
+    Inv: Fn(&T) -> T,
+    Id: Fn() -> T,
+    TIntoFolded: Fn(T) -> TFolded,
+{
+    /// 1-indexed で、 tree[i] は (i - lsb(i))..i の畳み込み
+    tree: Vec<T>,
+    group: QuickGroup<T, Op, Inv, Id>,
+    t_into_folded: TIntoFolded,
+}
+
+impl<T: Clone, TFolded, TIntoFolded, Op, Inv, Id> FenwickTree<T, TFolded, TIntoFolded, Op, Inv, Id>
+where
+    Op: Fn(&T, &T) -> T,
+    Inv: Fn(&T) -> T,
+    Id: Fn() -> T,
+    TIntoFolded: Fn(T) -> TFolded,
+{
+    #[inline]
+    pub fn set_value_folded<TFolded2>(
+        self,
+        value_folded: impl Fn(T) -> TFolded2,
+    ) -> FenwickTree<T, TFolded2, impl Fn(T) -> TFolded2, Op, Inv, Id> {
+        FenwickTree {
+            tree: self.tree,
+            group: self.group,
+            t_into_folded: value_folded,
+        }
+    }
+
+    #[inline]
+    pub fn map_value_folded<TFolded2>(
+        self,
+        map_fn: impl Fn(TFolded) -> TFolded2,
+    ) -> FenwickTree<T, TFolded2, impl Fn(T) -> TFolded2, Op, Inv, Id> {
+        FenwickTree {
+            tree: self.tree,
+            group: self.group,
+            t_into_folded: move |x| map_fn((self.t_into_folded)(x)),
+        }
+    }
+
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.tree.len() - 1
+    }
+
+    /// # 一点への加算
+    ///
+    /// `index` 番目の値 `v` を `op(v, x)` に置き換える。
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log N)$
+    pub fn add(&mut self, index: usize, x: T) {
+        assert!(index < self.size(), "index out of range: {}", index);
+        let mut i = index + 1;
+        while i < self.tree.len() {
+            self.tree[i] = self.group.op(&self.tree[i], &x);
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    fn prefix_inner(&self, r: usize) -> T {
+        let mut i = r;
+        let mut acc = self.group.id();
+        while i > 0 {
+            acc = self.group.op(&self.tree[i], &acc);
+            i &= i - 1;
+        }
+        acc
+    }
+
+    /// # 接頭辞の畳み込み
+    ///
+    /// `fold(..r)` と同じ。
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log N)$
+    #[inline]
+    pub fn prefix(&self, r: usize) -> TFolded {
+        assert!(r <= self.size(), "index out of range: {}", r);
+        (self.t_into_folded)(self.prefix_inner(r))
+    }
+
+    /// # 区間の畳み込み
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log N)$
+    pub fn fold(&self, range: impl IntoAccessRange<usize>) -> TFolded {
+        let range = range.into_access_range().into_range(self.size());
+        (self.t_into_folded)({
+            if range.start >= range.end {
+                self.group.id()
+            } else if range.start == 0 {
+                self.prefix_inner(range.end)
+            } else {
+                self.group.op(
+                    &self.group.inv(&self.prefix_inner(range.start)),
+                    &self.prefix_inner(range.end),
+                )
+            }
+        })
+    }
+
+    /// # 接頭辞の探索
+    ///
+    /// 単調な `cond_fn` について、 `cond_fn(prefix(r), r)` を満たす最大の `r` を返す。
+    /// `cond_fn(prefix(0), 0) == true` として扱われ、これが呼ばれることはない。
+    ///
+    /// # Panic-free preconditions
+    /// - `cond_fn(prefix(x), x)` は `x` について単調に `true` から `false` に変化する
+    ///
+    /// # 計算量
+    ///
+    /// $O(\log N)$
+    ///
+    /// # 例
+    /// ```
+    /// use fenwick_tree::fenwick_tree_by_add;
+    /// let ft = fenwick_tree_by_add(vec![3, 1, 4, 1, 5]);
+    /// assert_eq!(ft.find_prefix_to_end(|x, _| x <= 8), 3);
+    /// assert_eq!(ft.find_prefix_to_end(|x, _| x < 3), 0);
+    /// assert_eq!(ft.find_prefix_to_end(|_, r| r <= 4), 4);
+    /// assert_eq!(ft.find_prefix_to_end(|_, _| true), 5);
+    /// ```
+    pub fn find_prefix_to_end<F>(&self, cond_fn: F) -> usize
+    where
+        F: Fn(TFolded, usize) -> bool,
+    {
+        let n = self.size();
+        if n == 0 {
+            return 0;
+        }
+        let mut step = 1 << (usize::BITS - 1 - n.leading_zeros());
+        let mut pos = 0;
+        let mut acc = self.group.id();
+        while step > 0 {
+            if pos + step <= n {
+                let next = self.group.op(&acc, &self.tree[pos + step]);
+                if cond_fn((self.t_into_folded)(next.clone()), pos + step) {
+                    pos += step;
+                    acc = next;
+                }
+            }
+            step >>= 1;
+        }
+        pos
+    }
+}
+
+fn build_tree<T: Clone>(v: Vec<T>, id: T, op: impl Fn(&T, &T) -> T) -> Vec<T> {
+    let n = v.len();
+    let mut tree = Vec::with_capacity(n + 1);
+    tree.push(id);
+    tree.extend(v);
+    for i in 1..=n {
+        let j = i + (i & i.wrapping_neg());
+        if j <= n {
+            tree[j] = op(&tree[i], &tree[j]);
+        }
+    }
+    tree
+}
+
+/// # Fenwick Tree の構築 (`Group` による指定)
+///
+/// ## 計算量
+///
+/// $O(N)$
+#[inline]
+pub fn fenwick_tree<T>(
+    v: Vec<T>,
+) -> FenwickTree<T, T, impl Fn(T) -> T, impl Fn(&T, &T) -> T, impl Fn(&T) -> T, impl Fn() -> T>
+where
+    T: Clone + Group,
+{
+    let group = group_to_quick();
+    let tree = build_tree(v, group.id(), |a, b| group.op(a, b));
+    FenwickTree {
+        tree,
+        group,
+        t_into_folded: |x| x,
+    }
+}
+
+/// # Fenwick Tree の構築 (加法群)
+///
+/// ## 計算量
+///
+/// $O(N)$
+///
+/// ## 例
+/// ```
+/// use fenwick_tree::fenwick_tree_by_add;
+/// let mut ft = fenwick_tree_by_add(vec![1, 10, 20, -5, 3]);
+/// assert_eq!(ft.prefix(3), 31);
+/// assert_eq!(ft.fold(1..4), 25);
+/// ft.add(2, 100);
+/// assert_eq!(ft.fold(..), 129);
+/// assert_eq!(ft.fold(2..=2), 120);
+/// ```
+#[inline]
+pub fn fenwick_tree_by_add<T>(
+    v: Vec<T>,
+) -> FenwickTree<T, T, impl Fn(T) -> T, impl Fn(&T, &T) -> T, impl Fn(&T) -> T, impl Fn() -> T>
+where
+    T: CommutativeRing,
+{
+    let group = quick_group_by_add();
+    let tree = build_tree(v, group.id(), |a, b| group.op(a, b));
+    FenwickTree {
+        tree,
+        group,
+        t_into_folded: |x| x,
+    }
+}
+
+/// # Fenwick Tree の構築 (直接指定)
+///
+/// ## 計算量
+///
+/// $O(N)$
+pub fn fenwick_tree_by<T, Op, Inv, Id>(
+    v: Vec<T>,
+    op: Op,
+    inv: Inv,
+    id: Id,
+) -> FenwickTree<T, T, impl Fn(T) -> T, Op, Inv, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Inv: Fn(&T) -> T,
+    Id: Fn() -> T,
+{
+    let group = QuickGroup::new(op, inv, id);
+    let tree = build_tree(v, group.id(), |a, b| group.op(a, b));
+    FenwickTree {
+        tree,
+        group,
+        t_into_folded: |x| x,
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,88 @@
+#![allow(clippy::reversed_empty_ranges)]
+use crate::{fenwick_tree_by, fenwick_tree_by_add};
+use rand::{self, Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+#[test]
+fn test_fold() {
+    let ft = fenwick_tree_by_add(vec![1, 10, 20, -5, 3]);
+    assert_eq!(ft.size(), 5);
+    assert_eq!(ft.fold(0), 1);
+    assert_eq!(ft.fold(3..), -2);
+    assert_eq!(ft.fold(..), 29);
+    assert_eq!(ft.fold(..3), 31);
+    assert_eq!(ft.fold(1..1), 0);
+    assert_eq!(ft.fold(4..2), 0);
+    assert_eq!(ft.prefix(0), 0);
+    assert_eq!(ft.prefix(5), 29);
+}
+
+#[test]
+fn test_fenwick_tree_by() {
+    let v = vec![(1, 4), (-3, 4), (3, 9), (-4, 4)];
+    let mut ft = fenwick_tree_by(
+        v,
+        |a, b| (a.0 + b.0, a.1 + b.1),
+        |a| (-a.0, -a.1),
+        || (0, 0),
+    )
+    .map_value_folded(|x| x.0 * x.1);
+    assert_eq!(ft.fold(0..=1), -16);
+    assert_eq!(ft.fold(2..=3), -13);
+    ft.add(3, (4, 0));
+    assert_eq!(ft.fold(2..=3), 39);
+}
+
+#[test]
+fn test_random() {
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    for n in 0..=40 {
+        let mut v = (0..n).map(|_| rng.gen_range(0..1000)).collect::<Vec<i64>>();
+        let mut ft = fenwick_tree_by_add(v.clone());
+        for _ in 0..100 {
+            if n > 0 {
+                let i = rng.gen_range(0..n);
+                let x = rng.gen_range(0..1000);
+                ft.add(i, x);
+                v[i] += x;
+            }
+
+            let l = rng.gen_range(0..=n);
+            let r = rng.gen_range(l..=n);
+            assert_eq!(ft.fold(l..r), v[l..r].iter().sum::<i64>());
+
+            let bound = rng.gen_range(0..v.iter().sum::<i64>() + 10);
+            let expected = (0..=n)
+                .take_while(|&r| v[..r].iter().sum::<i64>() <= bound)
+                .last()
+                .unwrap();
+            assert_eq!(ft.find_prefix_to_end(|x, _| x <= bound), expected);
+        }
+    }
+}
+
+#[test]
+fn test_range_add_random() {
+    use crate::fenwick_tree_range_add;
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    for n in 0..=40 {
+        let mut v = (0..n)
+            .map(|_| rng.gen_range(-1000..1000))
+            .collect::<Vec<i64>>();
+        let mut ft = fenwick_tree_range_add(v.clone());
+        assert_eq!(ft.size(), n);
+        for _ in 0..100 {
+            let l = rng.gen_range(0..=n);
+            let r = rng.gen_range(l..=n);
+            let x = rng.gen_range(-1000..1000);
+            ft.add(l..r, x);
+            for e in v.iter_mut().take(r).skip(l) {
+                *e += x;
+            }
+
+            let l = rng.gen_range(0..=n);
+            let r = rng.gen_range(l..=n);
+            assert_eq!(ft.sum(l..r), v[l..r].iter().sum::<i64>());
+        }
+    }
+}
@@ -0,0 +1,350 @@
+use std::ops::Range;
+
+mod range;
+
+pub use range::*;
+
+/// # 重軽分解 (Heavy-Light Decomposition)
+///
+/// 根付き木の頂点を、各パスが $O(\log N)$ 個の連続区間に、
+/// 各部分木が 1 つの連続区間に分かれるように並べる。
+///
+/// 辺は子の側の頂点の位置に対応させる。
+/// このとき根の位置にはどの辺も対応しない。
+pub struct HeavyLightDecomposition {
+    parent: Vec<usize>,
+    depth: Vec<usize>,
+    /// 属する heavy path の最も根に近い頂点
+    head: Vec<usize>,
+    /// 頂点の位置
+    pos: Vec<usize>,
+    /// 位置にある頂点
+    order: Vec<usize>,
+    subtree_size: Vec<usize>,
+}
+
+impl HeavyLightDecomposition {
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.pos.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 根では `None`
+    #[inline]
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        let p = self.parent[v];
+        (p != usize::MAX).then_some(p)
+    }
+
+    #[inline]
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+
+    /// 頂点 `v` の位置
+    #[inline]
+    pub fn vertex(&self, v: usize) -> usize {
+        self.pos[v]
+    }
+
+    /// 位置 `i` にある頂点
+    #[inline]
+    pub fn vertex_at(&self, i: usize) -> usize {
+        self.order[i]
+    }
+
+    /// 辺 `u`-`v` の位置 (子の側の頂点の位置)
+    pub fn edge(&self, u: usize, v: usize) -> usize {
+        if self.parent[v] == u {
+            self.pos[v]
+        } else {
+            assert_eq!(self.parent[u], v, "hld: {}-{} is not an edge", u, v);
+            self.pos[u]
+        }
+    }
+
+    /// 部分木の頂点の位置の区間
+    #[inline]
+    pub fn subtree(&self, v: usize) -> Range<usize> {
+        self.pos[v]..self.pos[v] + self.subtree_size[v]
+    }
+
+    /// 部分木の辺の位置の区間
+    #[inline]
+    pub fn subtree_edges(&self, v: usize) -> Range<usize> {
+        self.pos[v] + 1..self.pos[v] + self.subtree_size[v]
+    }
+
+    /// 最小共通祖先
+    pub fn lca(&self, mut u: usize, mut v: usize) -> usize {
+        while self.head[u] != self.head[v] {
+            if self.depth[self.head[u]] > self.depth[self.head[v]] {
+                u = self.parent[self.head[u]];
+            } else {
+                v = self.parent[self.head[v]];
+            }
+        }
+        if self.depth[u] < self.depth[v] {
+            u
+        } else {
+            v
+        }
+    }
+
+    /// 2 頂点間の距離 (辺の数)
+    #[inline]
+    pub fn dist(&self, u: usize, v: usize) -> usize {
+        self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)]
+    }
+
+    /// # パスの分解
+    ///
+    /// `u` から `v` へのパスの頂点の位置を、たどる順に区間へ分解する。
+    /// 各区間について、位置の降順にたどるならば `true` を持つ。
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log N)$
+    #[inline]
+    pub fn path(&self, u: usize, v: usize) -> Vec<(Range<usize>, bool)> {
+        self.path_ranges(u, v, false)
+    }
+
+    /// [`path`](Self::path) の辺の位置の版
+    #[inline]
+    pub fn path_edges(&self, u: usize, v: usize) -> Vec<(Range<usize>, bool)> {
+        self.path_ranges(u, v, true)
+    }
+
+    fn path_ranges(&self, mut u: usize, mut v: usize, edge: bool) -> Vec<(Range<usize>, bool)> {
+        let mut up = Vec::new();
+        let mut down = Vec::new();
+        while self.head[u] != self.head[v] {
+            if self.depth[self.head[u]] >= self.depth[self.head[v]] {
+                up.push((self.pos[self.head[u]]..self.pos[u] + 1, true));
+                u = self.parent[self.head[u]];
+            } else {
+                down.push((self.pos[self.head[v]]..self.pos[v] + 1, false));
+                v = self.parent[self.head[v]];
+            }
+        }
+        let skip = edge as usize;
+        if self.pos[u] >= self.pos[v] {
+            up.push((self.pos[v] + skip..self.pos[u] + 1, true));
+        } else {
+            down.push((self.pos[u] + skip..self.pos[v] + 1, false));
+        }
+        up.into_iter()
+            .chain(down.into_iter().rev())
+            .filter(|(range, _)| !range.is_empty())
+            .collect()
+    }
+
+    /// # パスの畳み込み
+    ///
+    /// `u` から `v` へたどる順に頂点を畳み込む。
+    /// `rev` は区間の畳み込みを逆順にたどったものに変換する。
+    /// 可換なモノイドであれば恒等写像でよい。
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log N)$ 回の `range_fold`
+    #[inline]
+    pub fn path_fold<S: RangeFold>(
+        &self,
+        tree: &mut S,
+        u: usize,
+        v: usize,
+        op: impl Fn(&S::Folded, &S::Folded) -> S::Folded,
+        id: S::Folded,
+        rev: impl Fn(S::Folded) -> S::Folded,
+    ) -> S::Folded {
+        fold_ranges(tree, self.path(u, v), op, id, rev)
+    }
+
+    /// [`path_fold`](Self::path_fold) の辺の版
+    #[inline]
+    pub fn path_fold_edges<S: RangeFold>(
+        &self,
+        tree: &mut S,
+        u: usize,
+        v: usize,
+        op: impl Fn(&S::Folded, &S::Folded) -> S::Folded,
+        id: S::Folded,
+        rev: impl Fn(S::Folded) -> S::Folded,
+    ) -> S::Folded {
+        fold_ranges(tree, self.path_edges(u, v), op, id, rev)
+    }
+
+    /// # パスへの作用
+    ///
+    /// `u` から `v` へのパスの頂点に作用させる。
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log N)$ 回の `range_act`
+    pub fn path_act<S: RangeAct>(&self, tree: &mut S, u: usize, v: usize, a: S::Action)
+    where
+        S::Action: Clone,
+    {
+        for (range, _) in self.path(u, v) {
+            tree.range_act(range, a.clone());
+        }
+    }
+
+    /// [`path_act`](Self::path_act) の辺の版
+    pub fn path_act_edges<S: RangeAct>(&self, tree: &mut S, u: usize, v: usize, a: S::Action)
+    where
+        S::Action: Clone,
+    {
+        for (range, _) in self.path_edges(u, v) {
+            tree.range_act(range, a.clone());
+        }
+    }
+}
+
+fn fold_ranges<S: RangeFold>(
+    tree: &mut S,
+    ranges: Vec<(Range<usize>, bool)>,
+    op: impl Fn(&S::Folded, &S::Folded) -> S::Folded,
+    id: S::Folded,
+    rev: impl Fn(S::Folded) -> S::Folded,
+) -> S::Folded {
+    ranges.into_iter().fold(id, |acc, (range, reversed)| {
+        let x = tree.range_fold(range);
+        let x = if reversed { rev(x) } else { x };
+        op(&acc, &x)
+    })
+}
+
+/// # 重軽分解の構築
+///
+/// `adj` は無向木の隣接リスト。
+///
+/// ## Panics
+///
+/// `adj` が `root` を含む木でない (閉路、多重辺、非連結) とき
+///
+/// ## 計算量
+///
+/// $O(N)$
+///
+/// ## 例
+/// ```
+/// use hld::hld_new;
+/// use segment_tree::segment_tree_new;
+/// //     0
+/// //    / \
+/// //   1   2
+/// //  / \
+/// // 3   4
+/// let adj = vec![vec![1, 2], vec![0, 3, 4], vec![0], vec![1], vec![1]];
+/// let hld = hld_new(&adj, 0);
+/// assert_eq!(hld.lca(3, 4), 1);
+/// assert_eq!(hld.dist(3, 2), 3);
+///
+/// // 頂点の文字の連結 (非可換) を (順方向, 逆方向) で持つ
+/// type S = (String, String);
+/// fn op(a: &S, b: &S) -> S {
+///     (a.0.clone() + &b.0, b.1.clone() + &a.1)
+/// }
+/// fn id() -> S {
+///     (String::new(), String::new())
+/// }
+/// let name = ["a", "b", "c", "d", "e"];
+/// let mut values = vec![id(); 5];
+/// for v in 0..5 {
+///     values[hld.vertex(v)] = (name[v].to_string(), name[v].to_string());
+/// }
+/// let mut seg = segment_tree_new(values, op, id);
+/// for (u, v, expected) in [(3, 2, "dbac"), (2, 4, "cabe"), (1, 1, "b")] {
+///     let folded = hld.path_fold(&mut seg, u, v, op, id(), |(f, r)| (r, f));
+///     assert_eq!(folded.0, expected);
+/// }
+///
+/// let sub = hld.subtree(1);
+/// assert_eq!(sub.len(), 3);
+/// ```
+pub fn hld_new(adj: &[Vec<usize>], root: usize) -> HeavyLightDecomposition {
+    let n = adj.len();
+    let mut parent = vec![usize::MAX; n];
+    let mut depth = vec![0; n];
+    let mut subtree_size = vec![1; n];
+    if n == 0 {
+        return HeavyLightDecomposition {
+            parent,
+            depth,
+            head: Vec::new(),
+            pos: Vec::new(),
+            order: Vec::new(),
+            subtree_size,
+        };
+    }
+
+    // 行きがけ順
+    let mut preorder = Vec::with_capacity(n);
+    let mut visited = vec![false; n];
+    visited[root] = true;
+    let mut stack = vec![root];
+    while let Some(v) = stack.pop() {
+        preorder.push(v);
+        for &c in &adj[v] {
+            if c != parent[v] {
+                // 閉路や多重辺があると訪問済みの頂点に戻ってくる
+                assert!(!visited[c], "hld: graph is not a tree");
+                visited[c] = true;
+                parent[c] = v;
+                depth[c] = depth[v] + 1;
+                stack.push(c);
+            }
+        }
+    }
+    assert_eq!(preorder.len(), n, "hld: graph is not a tree");
+    let mut heavy = vec![usize::MAX; n];
+    for &v in preorder.iter().rev() {
+        let p = parent[v];
+        if p != usize::MAX {
+            subtree_size[p] += subtree_size[v];
+            if heavy[p] == usize::MAX || subtree_size[heavy[p]] < subtree_size[v] {
+                heavy[p] = v;
+            }
+        }
+    }
+
+    // heavy な子を最後に積んで、次に取り出されるようにする
+    let mut head = vec![root; n];
+    let mut pos = vec![0; n];
+    let mut order = Vec::with_capacity(n);
+    let mut stack = vec![root];
+    while let Some(v) = stack.pop() {
+        pos[v] = order.len();
+        order.push(v);
+        for &c in &adj[v] {
+            if c != parent[v] && c != heavy[v] {
+                head[c] = c;
+                stack.push(c);
+            }
+        }
+        if heavy[v] != usize::MAX {
+            head[heavy[v]] = head[v];
+            stack.push(heavy[v]);
+        }
+    }
+
+    HeavyLightDecomposition {
+        parent,
+        depth,
+        head,
+        pos,
+        order,
+        subtree_size,
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,99 @@
+use lazy_segment_tree::LazySegmentTree;
+use segment_tree::SegmentTree;
+use std::ops::Range;
+
+/// パス上の区間を畳み込めるもの
+pub trait RangeFold {
+    type Folded;
+    fn range_fold(&mut self, range: Range<usize>) -> Self::Folded;
+}
+
+/// パス上の区間に作用できるもの
+pub trait RangeAct {
+    type Action;
+    fn range_act(&mut self, range: Range<usize>, a: Self::Action);
+}
+
+impl<T, TFolded, TGetter, TSetter, TIntoFolded, TIntoGetter, TFromSetter, Op, Id> RangeFold
+    for SegmentTree<T, TFolded, TGetter, TSetter, TIntoFolded, TIntoGetter, TFromSetter, Op, Id>
+where
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+    TIntoFolded: Fn(T) -> TFolded,
+    TIntoGetter: Fn(T, usize) -> TGetter,
+    TFromSetter: Fn(TSetter, usize) -> T,
+{
+    type Folded = TFolded;
+    #[inline]
+    fn range_fold(&mut self, range: Range<usize>) -> TFolded {
+        self.fold(range)
+    }
+}
+
+macro_rules! impl_lazy {
+    ($trait:ident { $($body:tt)* }) => {
+        impl<
+                T,
+                TFolded,
+                TGetter,
+                TSetter,
+                A,
+                ASetter,
+                TIntoFolded,
+                TIntoGetter,
+                TFromSetter,
+                AFromSetter,
+                Op,
+                Id,
+                ActOp,
+                ActId,
+                ActApp,
+            > $trait
+            for LazySegmentTree<
+                T,
+                TFolded,
+                TGetter,
+                TSetter,
+                A,
+                ASetter,
+                TIntoFolded,
+                TIntoGetter,
+                TFromSetter,
+                AFromSetter,
+                Op,
+                Id,
+                ActOp,
+                ActId,
+                ActApp,
+            >
+        where
+            Op: Fn(&T, &T) -> T,
+            Id: Fn() -> T,
+            ActOp: Fn(&A, &A) -> A,
+            ActId: Fn() -> A,
+            ActApp: Fn(&A, &T) -> T,
+            TIntoFolded: Fn(T) -> TFolded,
+            TIntoGetter: Fn(T, usize) -> TGetter,
+            TFromSetter: Fn(TSetter, usize) -> T,
+            AFromSetter: Fn(ASetter) -> A,
+        {
+            $($body)*
+        }
+    };
+}
+
+impl_lazy!(RangeFold {
+    type Folded = TFolded;
+    #[inline]
+    fn range_fold(&mut self, range: Range<usize>) -> TFolded {
+        self.fold(range)
+    }
+});
+
+impl_lazy!(RangeAct {
+    type Action = ASetter;
+    #[inline]
+    fn range_act(&mut self, range: Range<usize>, a: ASetter) {
+        self.act(range, a)
+    }
+});
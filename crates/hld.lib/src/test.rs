@@ -0,0 +1,157 @@
+use crate::hld_new;
+use lazy_segment_tree::{lazy_segment_tree_new, LazySegmentTreeDyn};
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+use segment_tree::segment_tree_new;
+
+fn naive_path(parent: &[usize], depth: &[usize], mut u: usize, mut v: usize) -> Vec<usize> {
+    let mut up = vec![];
+    let mut down = vec![];
+    while u != v {
+        if depth[u] >= depth[v] {
+            up.push(u);
+            u = parent[u];
+        } else {
+            down.push(v);
+            v = parent[v];
+        }
+    }
+    up.push(u);
+    up.extend(down.into_iter().rev());
+    up
+}
+
+fn is_descendant(parent: &[usize], mut w: usize, v: usize) -> bool {
+    while w != usize::MAX && w != v {
+        w = parent[w];
+    }
+    w == v
+}
+
+fn range_add_sum(n: usize) -> LazySegmentTreeDyn<(i64, i64), i64, (i64, i64), (i64, i64), i64> {
+    lazy_segment_tree_new(
+        vec![(0_i64, 1_i64); n],
+        |a: &(i64, i64), b: &(i64, i64)| (a.0 + b.0, a.1 + b.1),
+        || (0, 0),
+        |a: &i64, b: &i64| a + b,
+        || 0,
+        |a: &i64, x: &(i64, i64)| (x.0 + a * x.1, x.1),
+    )
+    .set_value_folded(|x| x.0)
+    .into_dyn()
+}
+
+#[test]
+fn test_random() {
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    for n in 1..=30 {
+        let mut parent = vec![usize::MAX; n];
+        let mut depth = vec![0; n];
+        let mut adj = vec![vec![]; n];
+        for v in 1..n {
+            let p = rng.gen_range(0..v);
+            parent[v] = p;
+            depth[v] = depth[p] + 1;
+            adj[p].push(v);
+            adj[v].push(p);
+        }
+        let hld = hld_new(&adj, 0);
+        for v in 0..n {
+            assert_eq!(hld.vertex_at(hld.vertex(v)), v);
+            let sub = hld.subtree(v);
+            let count = (0..n).filter(|&w| is_descendant(&parent, w, v)).count();
+            assert_eq!(sub.len(), count);
+            assert_eq!(hld.subtree_edges(v).len(), count - 1);
+        }
+
+        // 頂点: 値の列を (順方向, 逆方向) の 4 進数として比較する
+        let values = (0..n).map(|_| rng.gen_range(0..4_u64)).collect::<Vec<_>>();
+        let op = |a: &(u64, u64, u64), b: &(u64, u64, u64)| {
+            (a.0 * b.2 + b.0, b.1 * a.2 + a.1, a.2 * b.2)
+        };
+        let mut seg_values = vec![(0, 0, 1); n];
+        for v in 0..n {
+            seg_values[hld.vertex(v)] = (values[v], values[v], 4);
+        }
+        let mut seg = segment_tree_new(seg_values, op, || (0, 0, 1));
+
+        // 頂点・辺: 区間加算、区間和
+        let mut vertex_values = vec![0_i64; n];
+        let mut vertex_lazy = range_add_sum(n);
+        let mut edge_values = vec![0_i64; n];
+        let mut lazy = range_add_sum(n);
+
+        for _ in 0..50 {
+            let u = rng.gen_range(0..n);
+            let v = rng.gen_range(0..n);
+            let path = naive_path(&parent, &depth, u, v);
+            if path.len() > 16 {
+                continue;
+            }
+            let expected = path.iter().fold(0, |acc, &w| acc * 4 + values[w]);
+            let folded = hld.path_fold(&mut seg, u, v, op, (0, 0, 1), |(f, r, p)| (r, f, p));
+            assert_eq!(folded.0, expected);
+
+            let x = rng.gen_range(-5..5);
+            hld.path_act(&mut vertex_lazy, u, v, x);
+            for &w in &path {
+                vertex_values[w] += x;
+            }
+            hld.path_act_edges(&mut lazy, u, v, x);
+            for w in path.windows(2) {
+                let child = if parent[w[0]] == w[1] { w[0] } else { w[1] };
+                edge_values[child] += x;
+            }
+
+            let a = rng.gen_range(0..n);
+            let b = rng.gen_range(0..n);
+            let path = naive_path(&parent, &depth, a, b);
+            let expected = path
+                .windows(2)
+                .map(|w| {
+                    let child = if parent[w[0]] == w[1] { w[0] } else { w[1] };
+                    edge_values[child]
+                })
+                .sum::<i64>();
+            let folded = hld.path_fold_edges(&mut lazy, a, b, |x, y| x + y, 0, |x| x);
+            assert_eq!(folded, expected);
+            assert_eq!(hld.dist(a, b), path.len() - 1);
+
+            // 部分木の頂点と辺
+            let c = rng.gen_range(0..n);
+            let descendants = (0..n)
+                .filter(|&w| is_descendant(&parent, w, c))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                vertex_lazy.fold(hld.subtree(c)),
+                descendants.iter().map(|&w| vertex_values[w]).sum::<i64>()
+            );
+            assert_eq!(
+                lazy.fold(hld.subtree_edges(c)),
+                descendants
+                    .iter()
+                    .filter(|&&w| w != c)
+                    .map(|&w| edge_values[w])
+                    .sum::<i64>()
+            );
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "hld: graph is not a tree")]
+fn test_cycle_panic() {
+    hld_new(&[vec![1, 2], vec![0, 2], vec![0, 1]], 0);
+}
+
+#[test]
+#[should_panic(expected = "hld: graph is not a tree")]
+fn test_multi_edge_panic() {
+    hld_new(&[vec![1, 1], vec![0, 0]], 0);
+}
+
+#[test]
+#[should_panic(expected = "hld: graph is not a tree")]
+fn test_disconnected_panic() {
+    hld_new(&[vec![1], vec![0], vec![]], 0);
+}
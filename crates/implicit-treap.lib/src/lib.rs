@@ -0,0 +1,539 @@
+//! # 暗黙の treap
+//!
+//! 列を、位置を暗黙のキーとする treap (優先度を乱数で決める平衡二分探索木) で持つ。
+//! キーを持たないので、要素の挿入・削除や列の分割・連結をしても振り直しは要らない。
+//! 任意の位置への挿入・削除、分割・連結、区間の反転、区間の畳み込み、
+//! 区間への作用をいずれも期待 $O(\log N)$ で行う。
+//!
+//! 区間の反転は、部分木に付けた反転フラグとして遅延させる。
+//! ノードの値は [`ReversibleFold`] で持つので、反転した区間の畳み込みも列の順になる。
+//! 作用は区間の反転と可換でなければならない (一括代入や一様な加算など)。
+//!
+//! ## `LazySegmentTree` との違い
+//!
+//! 区間の指定と `fold`, `act`, `get`, `set` の名前は `LazySegmentTree` にそろえているが、
+//! 値の射影 (`set_value_folded`, `set_value_getter`, `set_value_setter`,
+//! `set_action_setter` など) は持たず、モノイドの値をそのまま受け渡しする。
+//! 挿入・削除・反転によって要素の位置が変わるため、
+//! 位置を受け取る getter / setter が要素に対して定まらないからである。
+//! 必要な変換は呼び出し側で行う。
+
+use access_range::IntoAccessRange;
+use monoid::{Monoid, QuickMonoid};
+use monoid_action::{MonoidAction, QuickMonoidAction, QuickMonoidActionTrivial};
+use reversible_fold::ReversibleFold;
+use std::mem;
+
+type Link<T, A> = Option<Box<Node<T, A>>>;
+
+struct Node<T, A> {
+    left: Link<T, A>,
+    right: Link<T, A>,
+    priority: u64,
+    len: usize,
+    data: ReversibleFold<T, A>,
+}
+
+#[inline]
+fn len_of<T, A>(node: &Link<T, A>) -> usize {
+    node.as_ref().map_or(0, |node| node.len)
+}
+
+/// # 暗黙の treap
+pub struct ImplicitTreap<T, A, Op, Id, ActOp, ActId, ActApp>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+    ActOp: Fn(&A, &A) -> A,
+    ActId: Fn() -> A,
+    ActApp: Fn(&A, &T) -> T,
+{
+    root: Link<T, A>,
+    /// 優先度の乱数 (xorshift) の状態
+    seed: u64,
+    monoid_action: QuickMonoidAction<T, A, Op, Id, ActOp, ActId, ActApp>,
+}
+
+/// # 名前を付けられる暗黙の treap の型
+pub type ImplicitTreapStatic<T, A = ()> =
+    ImplicitTreap<T, A, fn(&T, &T) -> T, fn() -> T, fn(&A, &A) -> A, fn() -> A, fn(&A, &T) -> T>;
+
+impl<T, A, Op, Id, ActOp, ActId, ActApp> ImplicitTreap<T, A, Op, Id, ActOp, ActId, ActApp>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+    ActOp: Fn(&A, &A) -> A,
+    ActId: Fn() -> A,
+    ActApp: Fn(&A, &T) -> T,
+{
+    #[inline]
+    pub fn len(&self) -> usize {
+        len_of(&self.root)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    #[inline]
+    pub fn monoid_action(&self) -> &QuickMonoidAction<T, A, Op, Id, ActOp, ActId, ActApp> {
+        &self.monoid_action
+    }
+
+    /// # 挿入
+    ///
+    /// `index` の位置に `x` を挿入し、以降の要素を後ろにずらす。
+    ///
+    /// ## Panics
+    ///
+    /// - `index > len`
+    ///
+    /// ## 計算量
+    ///
+    /// 期待 $O(\log N)$
+    pub fn insert(&mut self, index: usize, x: T) {
+        assert!(index <= self.len(), "index out of range: {}", index);
+        let node = self.new_node(x);
+        let root = self.root.take();
+        let (l, r) = self.split(root, index);
+        let l = self.merge(l, node);
+        self.root = self.merge(l, r);
+    }
+
+    /// # 末尾への追加
+    ///
+    /// ## 計算量
+    ///
+    /// 期待 $O(\log N)$
+    #[inline]
+    pub fn push(&mut self, x: T) {
+        self.insert(self.len(), x);
+    }
+
+    /// # 削除
+    ///
+    /// `index` の位置の要素を取り除いて返し、以降の要素を前にずらす。
+    ///
+    /// ## Panics
+    ///
+    /// - `index >= len`
+    ///
+    /// ## 計算量
+    ///
+    /// 期待 $O(\log N)$
+    pub fn remove(&mut self, index: usize) -> T {
+        assert!(index < self.len(), "index out of range: {}", index);
+        let root = self.root.take();
+        let (l, r) = self.split(root, index);
+        let (m, r) = self.split(r, 1);
+        self.root = self.merge(l, r);
+        m.unwrap().data.value
+    }
+
+    /// # 分割
+    ///
+    /// `at` 以降の要素を取り出して返す。
+    ///
+    /// ## Panics
+    ///
+    /// - `at > len`
+    ///
+    /// ## 計算量
+    ///
+    /// 期待 $O(\log N)$
+    pub fn split_off(&mut self, at: usize) -> Self
+    where
+        Op: Clone,
+        Id: Clone,
+        ActOp: Clone,
+        ActId: Clone,
+        ActApp: Clone,
+    {
+        assert!(at <= self.len(), "index out of range: {}", at);
+        let root = self.root.take();
+        let (l, r) = self.split(root, at);
+        self.root = l;
+        let seed = self.next_priority();
+        let m = &self.monoid_action;
+        Self {
+            root: r,
+            seed,
+            monoid_action: QuickMonoidAction::new(
+                m.op.clone(),
+                m.id.clone(),
+                m.act_op.clone(),
+                m.act_id.clone(),
+                m.act_app.clone(),
+            ),
+        }
+    }
+
+    /// # 連結
+    ///
+    /// `other` のすべての要素を末尾に移す。 `other` は空になる。
+    ///
+    /// ## 計算量
+    ///
+    /// 期待 $O(\log N)$
+    pub fn append(&mut self, other: &mut Self) {
+        let r = other.root.take();
+        let root = self.root.take();
+        self.root = self.merge(root, r);
+    }
+
+    /// # 区間の反転
+    ///
+    /// ## 計算量
+    ///
+    /// 期待 $O(\log N)$
+    pub fn reverse(&mut self, range: impl IntoAccessRange<usize>) {
+        self.with_range(range, |_, node| node.data.reverse());
+    }
+
+    /// # 区間への作用
+    ///
+    /// ## 計算量
+    ///
+    /// 期待 $O(\log N)$
+    pub fn act(&mut self, range: impl IntoAccessRange<usize>, a: A) {
+        self.with_range(range, |m, node| node.data.apply(m, &a));
+    }
+
+    /// # 区間の畳み込み
+    ///
+    /// ## 計算量
+    ///
+    /// 期待 $O(\log N)$
+    pub fn fold(&mut self, range: impl IntoAccessRange<usize>) -> T {
+        self.with_range(range, |_, node| node.data.fold.clone())
+            .unwrap_or_else(|| self.monoid_action.id())
+    }
+
+    /// # 要素の取得
+    ///
+    /// ## 計算量
+    ///
+    /// 期待 $O(\log N)$
+    pub fn get(&mut self, index: usize) -> T {
+        assert!(index < self.len(), "index out of range: {}", index);
+        let mut root = self.root.take();
+        let mut node = root.as_mut().unwrap();
+        let mut index = index;
+        let v = loop {
+            self.propagate(node);
+            let l = len_of(&node.left);
+            if index < l {
+                node = node.left.as_mut().unwrap();
+            } else if index == l {
+                break node.data.value.clone();
+            } else {
+                index -= l + 1;
+                node = node.right.as_mut().unwrap();
+            }
+        };
+        self.root = root;
+        v
+    }
+
+    /// # 要素の変更
+    ///
+    /// ## 計算量
+    ///
+    /// 期待 $O(\log N)$
+    pub fn set(&mut self, index: usize, x: T) {
+        assert!(index < self.len(), "index out of range: {}", index);
+        let mut root = self.root.take();
+        self.set_node(root.as_mut().unwrap(), index, x);
+        self.root = root;
+    }
+
+    /// # 列全体の取得
+    ///
+    /// ## 計算量
+    ///
+    /// $O(N)$
+    pub fn to_vec(&mut self) -> Vec<T> {
+        let mut res = Vec::with_capacity(self.len());
+        let mut root = self.root.take();
+        if let Some(node) = root.as_mut() {
+            self.collect(node, &mut res);
+        }
+        self.root = root;
+        res
+    }
+
+    fn collect(&self, node: &mut Node<T, A>, res: &mut Vec<T>) {
+        self.propagate(node);
+        if let Some(l) = node.left.as_mut() {
+            self.collect(l, res);
+        }
+        res.push(node.data.value.clone());
+        if let Some(r) = node.right.as_mut() {
+            self.collect(r, res);
+        }
+    }
+
+    fn set_node(&self, node: &mut Node<T, A>, index: usize, x: T) {
+        self.propagate(node);
+        let l = len_of(&node.left);
+        if index < l {
+            self.set_node(node.left.as_mut().unwrap(), index, x);
+        } else if index == l {
+            node.data.value = x;
+        } else {
+            self.set_node(node.right.as_mut().unwrap(), index - l - 1, x);
+        }
+        self.update(node);
+    }
+
+    /// 区間を 1 つの部分木に切り出して `f` を呼ぶ。区間が空であれば `None` 。
+    fn with_range<R>(
+        &mut self,
+        range: impl IntoAccessRange<usize>,
+        f: impl FnOnce(&QuickMonoidAction<T, A, Op, Id, ActOp, ActId, ActApp>, &mut Node<T, A>) -> R,
+    ) -> Option<R> {
+        let range = range.into_access_range().into_range(self.len());
+        if range.start >= range.end {
+            return None;
+        }
+        let root = self.root.take();
+        let (l, r) = self.split(root, range.end);
+        let (l, mut m) = self.split(l, range.start);
+        let res = f(&self.monoid_action, m.as_mut().unwrap());
+        let l = self.merge(l, m);
+        self.root = self.merge(l, r);
+        Some(res)
+    }
+
+    fn next_priority(&mut self) -> u64 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        self.seed
+    }
+
+    fn new_node(&mut self, x: T) -> Link<T, A> {
+        Some(Box::new(Node {
+            left: None,
+            right: None,
+            priority: self.next_priority(),
+            len: 1,
+            data: ReversibleFold::new(x),
+        }))
+    }
+
+    /// 先頭 `k` 個とそれ以外に分ける
+    fn split(&self, node: Link<T, A>, k: usize) -> (Link<T, A>, Link<T, A>) {
+        let mut node = match node {
+            Some(node) => node,
+            None => return (None, None),
+        };
+        self.propagate(&mut node);
+        let l = len_of(&node.left);
+        if k <= l {
+            let (a, b) = self.split(node.left.take(), k);
+            node.left = b;
+            self.update(&mut node);
+            (a, Some(node))
+        } else {
+            let (a, b) = self.split(node.right.take(), k - l - 1);
+            node.right = a;
+            self.update(&mut node);
+            (Some(node), b)
+        }
+    }
+
+    fn merge(&self, a: Link<T, A>, b: Link<T, A>) -> Link<T, A> {
+        match (a, b) {
+            (None, b) => b,
+            (a, None) => a,
+            (Some(mut a), Some(mut b)) => {
+                if a.priority > b.priority {
+                    self.propagate(&mut a);
+                    a.right = self.merge(a.right.take(), Some(b));
+                    self.update(&mut a);
+                    Some(a)
+                } else {
+                    self.propagate(&mut b);
+                    b.left = self.merge(Some(a), b.left.take());
+                    self.update(&mut b);
+                    Some(b)
+                }
+            }
+        }
+    }
+
+    fn update(&self, node: &mut Node<T, A>) {
+        node.data.update(
+            &self.monoid_action,
+            node.left.as_ref().map(|l| &l.data),
+            node.right.as_ref().map(|r| &r.data),
+        );
+        node.len = len_of(&node.left) + 1 + len_of(&node.right);
+    }
+
+    fn propagate(&self, node: &mut Node<T, A>) {
+        let (l, r) = (node.left.as_deref_mut(), node.right.as_deref_mut());
+        if node.data.push_to(
+            &self.monoid_action,
+            l.map(|l| &mut l.data),
+            r.map(|r| &mut r.data),
+        ) {
+            mem::swap(&mut node.left, &mut node.right);
+        }
+    }
+}
+
+/// # 暗黙の treap の構築 (`QuickMonoidAction` による指定)
+///
+/// ## 計算量
+///
+/// 期待 $O(N \log N)$
+pub fn implicit_treap_new_quick_monoid_action<T, A, Op, Id, ActOp, ActId, ActApp>(
+    vec: Vec<T>,
+    monoid_action: QuickMonoidAction<T, A, Op, Id, ActOp, ActId, ActApp>,
+) -> ImplicitTreap<T, A, Op, Id, ActOp, ActId, ActApp>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+    ActOp: Fn(&A, &A) -> A,
+    ActId: Fn() -> A,
+    ActApp: Fn(&A, &T) -> T,
+{
+    let mut treap = ImplicitTreap {
+        root: None,
+        seed: 0x2545_f491_4f6c_dd1d,
+        monoid_action,
+    };
+    for x in vec {
+        treap.push(x);
+    }
+    treap
+}
+
+/// # 暗黙の treap の構築 (作用付き、直接指定)
+///
+/// ## 計算量
+///
+/// 期待 $O(N \log N)$
+///
+/// ## 例
+/// ```
+/// use implicit_treap::implicit_treap_new;
+/// // add max
+/// let mut treap = implicit_treap_new(
+///     vec![1, 4, 2, 3, 8, 3, 4],
+///     |a: &i32, b: &i32| *a.max(b),
+///     || 0,
+///     |x: &i32, y: &i32| x + y,
+///     || 0,
+///     |a: &i32, x: &i32| a + x,
+/// );
+/// assert_eq!(treap.fold(1..5), 8);
+/// treap.insert(2, 10);
+/// assert_eq!(treap.fold(1..5), 10);
+/// treap.act(..3, 5);
+/// assert_eq!(treap.to_vec(), vec![6, 9, 15, 2, 3, 8, 3, 4]);
+/// treap.reverse(2..6);
+/// assert_eq!(treap.to_vec(), vec![6, 9, 8, 3, 2, 15, 3, 4]);
+/// assert_eq!(treap.remove(5), 15);
+/// treap.set(0, 1);
+/// assert_eq!(treap.get(0), 1);
+/// assert_eq!(treap.fold(..), 9);
+/// ```
+#[inline]
+pub fn implicit_treap_new<T, A, Op, Id, ActOp, ActId, ActApp>(
+    vec: Vec<T>,
+    op: Op,
+    id: Id,
+    act_op: ActOp,
+    act_id: ActId,
+    act_app: ActApp,
+) -> ImplicitTreap<T, A, Op, Id, ActOp, ActId, ActApp>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+    ActOp: Fn(&A, &A) -> A,
+    ActId: Fn() -> A,
+    ActApp: Fn(&A, &T) -> T,
+{
+    implicit_treap_new_quick_monoid_action(
+        vec,
+        QuickMonoidAction::new(op, id, act_op, act_id, act_app),
+    )
+}
+
+/// # 暗黙の treap の構築 (`MonoidAction` による指定)
+///
+/// ## 計算量
+///
+/// 期待 $O(N \log N)$
+#[inline]
+pub fn implicit_treap_new_monoid_action<M>(vec: Vec<M::T>) -> ImplicitTreapStatic<M::T, M::A>
+where
+    M: MonoidAction,
+    M::T: Clone,
+{
+    implicit_treap_new_quick_monoid_action(vec, M::as_quick())
+}
+
+/// # 暗黙の treap の構築 (`QuickMonoid` による指定)
+///
+/// 作用は `()` となる。
+///
+/// ## 計算量
+///
+/// 期待 $O(N \log N)$
+///
+/// ## 例
+/// ```
+/// use implicit_treap::implicit_treap_new_quick_monoid;
+/// use monoid::QuickMonoid;
+/// // 文字列の連結 (非可換)
+/// let mut treap = implicit_treap_new_quick_monoid(
+///     "abcde".chars().map(|c| c.to_string()).collect(),
+///     QuickMonoid::new(|a: &String, b: &String| a.clone() + b, String::new),
+/// );
+/// treap.reverse(1..4);
+/// assert_eq!(treap.fold(..), "adcbe");
+/// let mut back = treap.split_off(2);
+/// assert_eq!(treap.fold(..), "ad");
+/// assert_eq!(back.fold(..), "cbe");
+/// back.append(&mut treap);
+/// assert_eq!(back.fold(1..), "bead");
+/// assert!(treap.is_empty());
+/// ```
+pub fn implicit_treap_new_quick_monoid<T, Op, Id>(
+    vec: Vec<T>,
+    monoid: QuickMonoid<T, Op, Id>,
+) -> ImplicitTreap<T, (), Op, Id, fn(&(), &()), fn(), fn(&(), &T) -> T>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+{
+    implicit_treap_new_quick_monoid_action(vec, QuickMonoidActionTrivial::from(monoid))
+}
+
+/// # 暗黙の treap の構築 (`Monoid` による指定)
+///
+/// 作用は `()` となる。
+///
+/// ## 計算量
+///
+/// 期待 $O(N \log N)$
+#[inline]
+pub fn implicit_treap_new_monoid<T>(vec: Vec<T>) -> ImplicitTreapStatic<T>
+where
+    T: Clone + Monoid,
+{
+    implicit_treap_new_quick_monoid(vec, T::as_quick())
+}
+
+#[cfg(test)]
+mod test;
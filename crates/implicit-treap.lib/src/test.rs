@@ -0,0 +1,143 @@
+use crate::{implicit_treap_new, implicit_treap_new_monoid};
+use monoid::Monoid;
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+/// 区間の (長さ, 総和, 位置で重み付けた和 `Σ i * x_i`)
+///
+/// 連結の順で重み付けた和が変わるので非可換。
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Weighted {
+    len: i64,
+    sum: i64,
+    weighted: i64,
+}
+
+impl Weighted {
+    fn single(x: i64) -> Self {
+        Weighted {
+            len: 1,
+            sum: x,
+            weighted: 0,
+        }
+    }
+
+    fn of(v: &[i64]) -> Self {
+        Weighted {
+            len: v.len() as i64,
+            sum: v.iter().sum(),
+            weighted: v.iter().enumerate().map(|(i, &x)| i as i64 * x).sum(),
+        }
+    }
+
+    /// 各要素に `c` を加える
+    fn add(&self, c: i64) -> Self {
+        Weighted {
+            len: self.len,
+            sum: self.sum + c * self.len,
+            weighted: self.weighted + c * (self.len * (self.len - 1) / 2),
+        }
+    }
+}
+
+impl Monoid for Weighted {
+    fn op(&self, other: &Self) -> Self {
+        Weighted {
+            len: self.len + other.len,
+            sum: self.sum + other.sum,
+            weighted: self.weighted + other.weighted + self.len * other.sum,
+        }
+    }
+    fn id() -> Self {
+        Weighted {
+            len: 0,
+            sum: 0,
+            weighted: 0,
+        }
+    }
+}
+
+#[test]
+fn test_random() {
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    for n in 0..=20 {
+        let mut v = (0..n).map(|_| rng.gen_range(-9..10)).collect::<Vec<i64>>();
+        // 区間への一様な加算を作用とする
+        let mut treap = implicit_treap_new(
+            v.iter().map(|&x| Weighted::single(x)).collect(),
+            Weighted::op,
+            Weighted::id,
+            |a: &i64, b: &i64| a + b,
+            || 0,
+            |&a: &i64, x: &Weighted| x.add(a),
+        );
+        for _ in 0..300 {
+            let len = v.len();
+            let l = rng.gen_range(0..=len);
+            let r = rng.gen_range(l..=len);
+            match rng.gen_range(0..8) {
+                0 => {
+                    let x = rng.gen_range(-9..10);
+                    treap.insert(l, Weighted::single(x));
+                    v.insert(l, x);
+                }
+                1 => {
+                    if l < len {
+                        assert_eq!(treap.remove(l).sum, v.remove(l));
+                    }
+                }
+                2 => {
+                    assert_eq!(treap.fold(l..r), Weighted::of(&v[l..r]));
+                }
+                3 => {
+                    let c = rng.gen_range(-9..10);
+                    treap.act(l..r, c);
+                    v[l..r].iter_mut().for_each(|x| *x += c);
+                }
+                4 => {
+                    treap.reverse(l..r);
+                    v[l..r].reverse();
+                }
+                5 => {
+                    if l < len {
+                        let x = rng.gen_range(-9..10);
+                        treap.set(l, Weighted::single(x));
+                        v[l] = x;
+                        assert_eq!(treap.get(l).sum, x);
+                    }
+                }
+                6 => {
+                    // 後半を切り離して前に付け直す
+                    let mut back = treap.split_off(l);
+                    assert_eq!((treap.len(), back.len()), (l, len - l));
+                    back.append(&mut treap);
+                    assert!(treap.is_empty());
+                    treap = back;
+                    v.rotate_left(l);
+                }
+                _ => {
+                    let got = treap.to_vec().iter().map(|x| x.sum).collect::<Vec<_>>();
+                    assert_eq!(got, v);
+                }
+            }
+            assert_eq!(treap.len(), v.len());
+            assert_eq!(treap.fold(..), Weighted::of(&v));
+        }
+    }
+}
+
+#[test]
+fn test_monoid() {
+    let mut treap = implicit_treap_new_monoid([3, 1, 4, 1, 5].map(Weighted::single).to_vec());
+    assert_eq!(treap.fold(..).weighted, 1 + 8 + 3 + 20);
+    treap.reverse(..);
+    assert_eq!(treap.fold(..).weighted, 1 + 8 + 3 + 12);
+    treap.push(Weighted::single(9));
+    treap.insert(0, Weighted::single(2));
+    assert_eq!(
+        treap.to_vec().iter().map(|x| x.sum).collect::<Vec<_>>(),
+        vec![2, 5, 1, 4, 1, 3, 9]
+    );
+    assert_eq!(treap.fold(1..3), Weighted::of(&[5, 1]));
+    assert_eq!(treap.fold(3..3), Weighted::id());
+}
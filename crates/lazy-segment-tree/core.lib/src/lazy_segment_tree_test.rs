@@ -48,3 +48,55 @@ fn test_add_full() {
         }
     }
 }
+
+#[test]
+fn test_find_index_add_sum_full() {
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    for n in 1..=40 {
+        let mut v = (0..n).map(|_| rng.gen_range(0..100)).collect::<Vec<i64>>();
+        let mut seg = lazy_segment_tree_new_with_len(
+            v.clone(),
+            |a, b| a + b,
+            || 0,
+            |x: &i64, y: &i64| x + y,
+            || 0_i64,
+            |x, a, s| x * (s as i64) + a,
+        );
+
+        for _ in 0..100 {
+            let l = rng.gen_range(0..n);
+            let r = rng.gen_range(l..=n);
+            let a = rng.gen_range(0..100);
+            seg.act(l..r, a);
+            for e in v.iter_mut().take(r).skip(l) {
+                *e += a;
+            }
+
+            let t = rng.gen_range(0..(n as i64) * 100);
+
+            let l = rng.gen_range(0..n);
+            let mut expected = l;
+            while expected < n && v[l..=expected].iter().sum::<i64>() <= t {
+                expected += 1;
+            }
+            let got = seg.find_index_to_end(l, |x, r| {
+                assert!(l < r && r <= n, "l={l}, r={r}, n={n}");
+                assert_eq!(x, v[l..r].iter().sum::<i64>());
+                x <= t
+            });
+            assert_eq!(got, expected, "n={n}, l={l}, t={t}");
+
+            let r = rng.gen_range(0..=n);
+            let mut expected = r;
+            while expected > 0 && v[expected - 1..r].iter().sum::<i64>() <= t {
+                expected -= 1;
+            }
+            let got = seg.find_index_to_start(r, |x, l| {
+                assert!(l < r, "l={l}, r={r}, n={n}");
+                assert_eq!(x, v[l..r].iter().sum::<i64>());
+                x <= t
+            });
+            assert_eq!(got, expected, "n={n}, r={r}, t={t}");
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use access_range::IntoAccessRange;
 use ceil_log2::ceil_log2_usize;
 use lazy_segment_tree_util_type::lazy_seg_type;
-use monoid_action::QuickMonoidAction;
+use monoid_action::{MonoidAction, QuickMonoidAction};
 use std::mem;
 
 pub struct LazySegmentTree<
@@ -45,6 +45,79 @@ pub struct LazySegmentTree<
     phantom: std::marker::PhantomData<(TSetter, ASetter)>,
 }
 
+/// # 名前を付けられる遅延セグメントツリーの型
+///
+/// 関数をすべて `fn` ポインタとして持つ `LazySegmentTree` 。
+/// `lazy_seg_type!` は `impl Fn` を含むため構造体のフィールドなどには書けないが、こちらは書ける。
+/// 型引数の順序と省略時の値は `lazy_seg_type!` と同じ。
+///
+/// ## 例
+///
+/// ```
+/// use lazy_segment_tree::{lazy_segment_tree_new_static, LazySegmentTreeStatic};
+///
+/// struct Solver {
+///     // add max
+///     seg: LazySegmentTreeStatic<i64>,
+/// }
+///
+/// let mut solver = Solver {
+///     seg: lazy_segment_tree_new_static(
+///         vec![1, 4, 2, 3],
+///         |a, b| *a.max(b),
+///         || i64::MIN,
+///         |x, y| x + y,
+///         || 0,
+///         |a, x| a + x,
+///     ),
+/// };
+/// solver.seg.act(2.., 10);
+/// assert_eq!(solver.seg.fold(..), 13);
+/// ```
+pub type LazySegmentTreeStatic<T, TFolded = T, TGetter = T, TSetter = T, A = T, ASetter = A> =
+    LazySegmentTree<
+        T,
+        TFolded,
+        TGetter,
+        TSetter,
+        A,
+        ASetter,
+        fn(T) -> TFolded,
+        fn(T, usize) -> TGetter,
+        fn(TSetter, usize) -> T,
+        fn(ASetter) -> A,
+        fn(&T, &T) -> T,
+        fn() -> T,
+        fn(&A, &A) -> A,
+        fn() -> A,
+        fn(&A, &T) -> T,
+    >;
+
+/// # 関数をボックス化した遅延セグメントツリーの型
+///
+/// 関数をすべて `Box<dyn Fn>` として持つ `LazySegmentTree` 。
+/// キャプチャを持つ関数で構築したもの (ユーティリティのビルダーなど) も
+/// [`LazySegmentTree::into_dyn`] によってこの型に変換すれば名前を付けられる。
+/// 型引数の順序と省略時の値は `lazy_seg_type!` と同じ。
+pub type LazySegmentTreeDyn<T, TFolded = T, TGetter = T, TSetter = T, A = T, ASetter = A> =
+    LazySegmentTree<
+        T,
+        TFolded,
+        TGetter,
+        TSetter,
+        A,
+        ASetter,
+        Box<dyn Fn(T) -> TFolded>,
+        Box<dyn Fn(T, usize) -> TGetter>,
+        Box<dyn Fn(TSetter, usize) -> T>,
+        Box<dyn Fn(ASetter) -> A>,
+        Box<dyn Fn(&T, &T) -> T>,
+        Box<dyn Fn() -> T>,
+        Box<dyn Fn(&A, &A) -> A>,
+        Box<dyn Fn() -> A>,
+        Box<dyn Fn(&A, &T) -> T>,
+    >;
+
 impl<
         T,
         TFolded,
@@ -97,6 +170,12 @@ where
         index + self.size_pow2
     }
 
+    #[inline(always)]
+    fn index_of_leaf(&self, tree_index: usize) -> usize {
+        debug_assert!(self.size_pow2 <= tree_index && tree_index <= self.tree.len());
+        tree_index - self.size_pow2
+    }
+
     #[inline(always)]
     fn is_leaf(&self, tree_index: usize) -> bool {
         debug_assert!(1 <= tree_index && tree_index < self.tree.len());
@@ -112,17 +191,66 @@ where
         &self.monoid_action
     }
 
-    pub fn set_value_folded<TFolded2>(
+    /// # 関数のボックス化
+    ///
+    /// 名前を付けられる [`LazySegmentTreeDyn`] に変換する。
+    pub fn into_dyn(self) -> LazySegmentTreeDyn<T, TFolded, TGetter, TSetter, A, ASetter>
+    where
+        TIntoFolded: 'static,
+        TIntoGetter: 'static,
+        TFromSetter: 'static,
+        AFromSetter: 'static,
+        Op: 'static,
+        Id: 'static,
+        ActOp: 'static,
+        ActId: 'static,
+        ActApp: 'static,
+    {
+        let m = self.monoid_action;
+        LazySegmentTree {
+            monoid_action: QuickMonoidAction::new(
+                Box::new(m.op),
+                Box::new(m.id),
+                Box::new(m.act_op),
+                Box::new(m.act_id),
+                Box::new(m.act_app),
+            ),
+            tree: self.tree,
+            lazy: self.lazy,
+            size: self.size,
+            size_pow2: self.size_pow2,
+
+            t_into_folded: Box::new(self.t_into_folded),
+            t_into_getter: Box::new(self.t_into_getter),
+            t_from_setter: Box::new(self.t_from_setter),
+            a_from_setter: Box::new(self.a_from_setter),
+            phantom: Default::default(),
+        }
+    }
+
+    pub fn set_value_folded<TFolded2, TIntoFolded2>(
         self,
-        fn_getter: impl Fn(T) -> TFolded2,
-    ) -> lazy_seg_type!(
-        T = T,
-        TFolded = TFolded2,
-        TGetter = TGetter,
-        TSetter = TSetter,
-        A = A,
-        ASetter = ASetter,
-    ) {
+        fn_getter: TIntoFolded2,
+    ) -> LazySegmentTree<
+        T,
+        TFolded2,
+        TGetter,
+        TSetter,
+        A,
+        ASetter,
+        TIntoFolded2,
+        TIntoGetter,
+        TFromSetter,
+        AFromSetter,
+        Op,
+        Id,
+        ActOp,
+        ActId,
+        ActApp,
+    >
+    where
+        TIntoFolded2: Fn(T) -> TFolded2,
+    {
         LazySegmentTree {
             monoid_action: self.monoid_action,
             tree: self.tree,
@@ -138,17 +266,29 @@ where
         }
     }
 
-    pub fn set_value_getter<TGetter2>(
+    pub fn set_value_getter<TGetter2, TIntoGetter2>(
         self,
-        fn_getter: impl Fn(T, usize) -> TGetter2,
-    ) -> lazy_seg_type!(
-        T = T,
-        TFolded = TFolded,
-        TGetter = TGetter2,
-        TSetter = TSetter,
-        A = A,
-        ASetter = ASetter,
-    ) {
+        fn_getter: TIntoGetter2,
+    ) -> LazySegmentTree<
+        T,
+        TFolded,
+        TGetter2,
+        TSetter,
+        A,
+        ASetter,
+        TIntoFolded,
+        TIntoGetter2,
+        TFromSetter,
+        AFromSetter,
+        Op,
+        Id,
+        ActOp,
+        ActId,
+        ActApp,
+    >
+    where
+        TIntoGetter2: Fn(T, usize) -> TGetter2,
+    {
         LazySegmentTree {
             monoid_action: self.monoid_action,
             tree: self.tree,
@@ -164,17 +304,29 @@ where
         }
     }
 
-    pub fn set_value_setter<TSetter2>(
+    pub fn set_value_setter<TSetter2, TFromSetter2>(
         self,
-        fn_setter: impl Fn(TSetter2, usize) -> T,
-    ) -> lazy_seg_type!(
-        T = T,
-        TFolded = TFolded,
-        TGetter = TGetter,
-        TSetter = TSetter2,
-        A = A,
-        ASetter = ASetter,
-    ) {
+        fn_setter: TFromSetter2,
+    ) -> LazySegmentTree<
+        T,
+        TFolded,
+        TGetter,
+        TSetter2,
+        A,
+        ASetter,
+        TIntoFolded,
+        TIntoGetter,
+        TFromSetter2,
+        AFromSetter,
+        Op,
+        Id,
+        ActOp,
+        ActId,
+        ActApp,
+    >
+    where
+        TFromSetter2: Fn(TSetter2, usize) -> T,
+    {
         LazySegmentTree {
             monoid_action: self.monoid_action,
             tree: self.tree,
@@ -269,17 +421,29 @@ where
     }
 
     #[inline]
-    pub fn set_action_setter<ASetter2>(
+    pub fn set_action_setter<ASetter2, AFromSetter2>(
         self,
-        fn_setter: impl Fn(ASetter2) -> A,
-    ) -> lazy_seg_type!(
-        T = T,
-        TFolded = TFolded,
-        TGetter = TGetter,
-        TSetter = TSetter,
-        A = A,
-        ASetter = ASetter2,
-    ) {
+        fn_setter: AFromSetter2,
+    ) -> LazySegmentTree<
+        T,
+        TFolded,
+        TGetter,
+        TSetter,
+        A,
+        ASetter2,
+        TIntoFolded,
+        TIntoGetter,
+        TFromSetter,
+        AFromSetter2,
+        Op,
+        Id,
+        ActOp,
+        ActId,
+        ActApp,
+    >
+    where
+        AFromSetter2: Fn(ASetter2) -> A,
+    {
         LazySegmentTree {
             monoid_action: self.monoid_action,
             tree: self.tree,
@@ -500,6 +664,234 @@ where
         let v = self.fold_inner(index);
         (self.t_into_getter)(v, index)
     }
+
+    /// # 始端に向けて探す探索
+    /// 単調な `cond_fn` と `r` について、 `cond_fn(fold(l..r), l)` を満たす
+    /// `r` 未満で最小の値 `l` を返す。
+    /// そのような値がなければ `r` を返す。 `cond_fn(fold(r..r), r) == true` であれば整合するが、これが呼ばれることはない。
+    ///
+    /// 条件や返り値は `SegmentTree::find_index_to_start` と同じであり、遅延されている作用を伝播しながら探索する。
+    ///
+    /// # Panic-free preconditions
+    /// - `r <= self.size()`
+    /// - `cond_fn(fold(x..r), x)` は `x` の減少について単調に `true` から `false` に変化する
+    ///
+    /// # 計算量
+    ///
+    /// $N$ を `size()` とする。
+    /// - ステップ数、 `cond_fn` の呼び出し回数、 `op`, `act_op`, `act_app` の呼び出し回数がすべて $O(\log N)$
+    ///
+    /// # 例
+    /// ```
+    /// use lazy_segment_tree::lazy_segment_tree_new;
+    /// // add max
+    /// let mut seg = lazy_segment_tree_new(
+    ///     vec![1, 4, 2, 3, 8, 3, 4],
+    ///     |a, b| a.max(b).clone(),
+    ///     || 0,
+    ///     |x, y| x + y,
+    ///     || 0,
+    ///     |a, x| a + x,
+    /// );
+    /// assert_eq!(seg.find_index_to_start(4, |x, _| x < 4), 2);
+    /// seg.act(..2, -3);
+    /// assert_eq!(seg.find_index_to_start(4, |x, _| x < 4), 0);
+    /// ```
+    #[inline]
+    pub fn find_index_to_start<F>(&mut self, r: usize, cond_fn: F) -> usize
+    where
+        F: Fn(TFolded, usize) -> bool,
+    {
+        assert!(
+            r <= self.size,
+            "r out of range: r={}, len={}",
+            r,
+            self.size(),
+        );
+        if r == 0 {
+            return 0;
+        }
+        // `r-1` の葉までの経路上にある作用を伝播しておくことで、
+        // 以降に訪れるノードは親が評価済みであることが保証される
+        unsafe { self.eval_down_unchecked(r - 1) };
+        let mut done = self.monoid_action.id();
+        let mut done_l = r;
+        let mut cur = self.leaf_of(r - 1);
+        let mut cur_len: usize = 1;
+        loop {
+            // 不変量:
+            // - `fold(done_l..r) == done`
+            // - `cur_len` はノード `cur` の長さ
+            // - `done_l` 超過に答えはない
+
+            macro_rules! cond {
+                () => {
+                    cond_fn(
+                        (self.t_into_folded)(
+                            self.monoid_action
+                                .op(unsafe { self.tree.get_unchecked(cur) }, &done),
+                        ),
+                        done_l - cur_len,
+                    )
+                };
+            }
+
+            macro_rules! go_left {
+                () => {
+                    done = self
+                        .monoid_action
+                        .op(unsafe { self.tree.get_unchecked(cur) }, &done);
+                    done_l -= cur_len;
+                    cur -= 1;
+                };
+            }
+
+            // ノード `cur` が右側の子である間、 `cur` を親に置き換える
+            while cur != 1 && cur & 1 == 1 {
+                cur >>= 1;
+                cur_len *= 2;
+            }
+            unsafe { self.eval_unchecked(cur) };
+            if !cond!() {
+                // 現在の `cur` の左端は対象ではないから、
+                // `cur` ノードの葉に対応するどれかが対象であることがわかる
+
+                while !self.is_leaf(cur) {
+                    cur = cur * 2 + 1;
+                    cur_len /= 2;
+                    unsafe { self.eval_unchecked(cur) };
+                    if cond!() {
+                        go_left!();
+                        unsafe { self.eval_unchecked(cur) };
+                    }
+                }
+                return self.index_of_leaf(cur) + 1;
+            }
+
+            // `cur` が2冪であれば、 `cur` がその高さにおける左端まで行ったということなので終了
+            if cur & 0_usize.wrapping_sub(cur) == cur {
+                return 0;
+            }
+
+            go_left!();
+            cur >>= 1;
+            cur_len *= 2;
+        }
+    }
+
+    /// # 終端に向けて探す探索
+    /// 単調な `cond_fn` と `l` について、 `cond_fn(fold(l..r), r)` を満たす
+    /// `l+1` 以上 `size()` 以下で最大の値 `r` を返す。
+    /// そのような値がなければ `l` を返す。`cond_fn(fold(l..l), l) == true` であれば整合するが、これが呼ばれることはない。
+    ///
+    /// 条件や返り値は `SegmentTree::find_index_to_end` と同じであり、遅延されている作用を伝播しながら探索する。
+    ///
+    /// # Panic-free preconditions
+    /// - `l < self.size()`
+    /// - `cond_fn(fold(l..x), x)` は `x` について単調に `true` から `false` に変化する
+    ///
+    /// # 計算量
+    ///
+    /// $N$ を `size()` とする。
+    /// - ステップ数、 `cond_fn` の呼び出し回数、 `op`, `act_op`, `act_app` の呼び出し回数がすべて $O(\log N)$
+    ///
+    /// # 例
+    /// ```
+    /// use lazy_segment_tree::lazy_segment_tree_new;
+    /// // add sum (長さを持たせる)
+    /// let mut seg = lazy_segment_tree_new(
+    ///     vec![(1, 1), (4, 1), (2, 1), (3, 1), (8, 1)],
+    ///     |a: &(i32, i32), b: &(i32, i32)| (a.0 + b.0, a.1 + b.1),
+    ///     || (0, 0),
+    ///     |x, y| x + y,
+    ///     || 0,
+    ///     |a, x| (x.0 + a * x.1, x.1),
+    /// );
+    /// // 和が 10 以下である最長の接頭辞
+    /// assert_eq!(seg.find_index_to_end(0, |x, _| x.0 <= 10), 4);
+    /// seg.act(1..3, 2);
+    /// assert_eq!(seg.find_index_to_end(0, |x, _| x.0 <= 10), 2);
+    /// ```
+    #[inline]
+    pub fn find_index_to_end<F>(&mut self, l: usize, cond_fn: F) -> usize
+    where
+        F: Fn(TFolded, usize) -> bool,
+    {
+        assert!(
+            l < self.size(),
+            "l out of range: l={}, len={}",
+            l,
+            self.size(),
+        );
+        // `l` の葉までの経路上にある作用を伝播しておくことで、
+        // 以降に訪れるノードは親が評価済みであることが保証される
+        unsafe { self.eval_down_unchecked(l) };
+        let mut done = self.monoid_action.id();
+        let mut done_r = l;
+        let mut cur = self.leaf_of(l);
+        let mut cur_len: usize = 1;
+        loop {
+            // 不変量:
+            // - `fold(l..done_r) == done`
+            // - `cur_len` はノード `cur` の長さ
+            // - `done_r` 未満に答えはない
+
+            macro_rules! cond {
+                () => {
+                    (done_r + cur_len) <= self.size
+                        && cond_fn(
+                            (self.t_into_folded)(
+                                self.monoid_action
+                                    .op(&done, unsafe { self.tree.get_unchecked(cur) }),
+                            ),
+                            done_r + cur_len,
+                        )
+                };
+            }
+
+            macro_rules! go_right {
+                () => {
+                    done = self
+                        .monoid_action
+                        .op(&done, unsafe { self.tree.get_unchecked(cur) });
+                    done_r += cur_len;
+                    cur += 1;
+                };
+            }
+
+            // ノード `cur` が左側の子である間、 `cur` を親に置き換える
+            while cur & 1 == 0 {
+                cur >>= 1;
+                cur_len *= 2;
+            }
+            unsafe { self.eval_unchecked(cur) };
+            if !cond!() {
+                // 現在の `cur` の右端は対象ではないから、
+                // `cur` 内のノードの葉に対応するどれかが対象であることがわかる
+
+                while !self.is_leaf(cur) {
+                    cur *= 2;
+                    cur_len /= 2;
+                    unsafe { self.eval_unchecked(cur) };
+                    if cond!() {
+                        go_right!();
+                        unsafe { self.eval_unchecked(cur) };
+                    }
+                }
+                return self.index_of_leaf(cur);
+            }
+
+            go_right!();
+
+            // `cur` が2冪であれば、 `cur-1` がその高さにおける右端まで行ったということなので終了
+            if cur & 0_usize.wrapping_sub(cur) == cur {
+                return self.size;
+            }
+
+            cur >>= 1;
+            cur_len *= 2;
+        }
+    }
 }
 
 /// # 遅延セグメントツリーの構築 (直接指定)
@@ -541,6 +933,61 @@ pub fn lazy_segment_tree_new<T, A, Op, Id, ActOp, ActId, ActApp>(
     act_id: ActId,
     act_app: ActApp,
 ) -> lazy_seg_type!(T = T, A = A)
+where
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+    ActOp: Fn(&A, &A) -> A,
+    ActId: Fn() -> A,
+    ActApp: Fn(&A, &T) -> T,
+{
+    lazy_segment_tree_new_internal(vec, op, id, act_op, act_id, act_app)
+}
+
+/// # 遅延セグメントツリーの構築 (`fn` ポインタによる指定)
+///
+/// `lazy_segment_tree_new` と同じだが、名前を付けられる `LazySegmentTreeStatic` を返す。
+/// 各関数にはキャプチャを持たないクロージャか関数を指定する。
+///
+/// ## 計算量
+///
+/// $O(N)$
+#[inline]
+pub fn lazy_segment_tree_new_static<T, A>(
+    vec: Vec<T>,
+    op: fn(&T, &T) -> T,
+    id: fn() -> T,
+    act_op: fn(&A, &A) -> A,
+    act_id: fn() -> A,
+    act_app: fn(&A, &T) -> T,
+) -> LazySegmentTreeStatic<T, T, T, T, A> {
+    lazy_segment_tree_new_internal(vec, op, id, act_op, act_id, act_app)
+}
+
+#[inline]
+fn lazy_segment_tree_new_internal<T, A, Op, Id, ActOp, ActId, ActApp>(
+    vec: Vec<T>,
+    op: Op,
+    id: Id,
+    act_op: ActOp,
+    act_id: ActId,
+    act_app: ActApp,
+) -> LazySegmentTree<
+    T,
+    T,
+    T,
+    T,
+    A,
+    A,
+    fn(T) -> T,
+    fn(T, usize) -> T,
+    fn(T, usize) -> T,
+    fn(A) -> A,
+    Op,
+    Id,
+    ActOp,
+    ActId,
+    ActApp,
+>
 where
     Op: Fn(&T, &T) -> T,
     Id: Fn() -> T,
@@ -566,10 +1013,10 @@ where
             size: len,
             size_pow2: 0,
 
-            t_into_folded: id_fn,
-            t_into_getter: id_fn_idx,
-            t_from_setter: id_fn_idx,
-            a_from_setter: id_fn,
+            t_into_folded: id_fn as fn(T) -> T,
+            t_into_getter: id_fn_idx as fn(T, usize) -> T,
+            t_from_setter: id_fn_idx as fn(T, usize) -> T,
+            a_from_setter: id_fn as fn(A) -> A,
             phantom: Default::default(),
         };
     }
@@ -605,13 +1052,85 @@ where
         size: len,
         size_pow2: len2,
 
-        t_into_folded: id_fn,
-        t_into_getter: id_fn_idx,
-        t_from_setter: id_fn_idx,
-        a_from_setter: id_fn,
+        t_into_folded: id_fn as fn(T) -> T,
+        t_into_getter: id_fn_idx as fn(T, usize) -> T,
+        t_from_setter: id_fn_idx as fn(T, usize) -> T,
+        a_from_setter: id_fn as fn(A) -> A,
         phantom: Default::default(),
     }
 }
 
+/// # 遅延セグメントツリーの構築 (モノイドアクションの型による指定)
+///
+/// `MonoidAction` を実装した型によって、モノイドと作用をまとめて指定する。
+///
+/// ## 計算量
+///
+/// $O(N)$
+///
+/// ## 例
+///
+/// ```
+/// use lazy_segment_tree::lazy_segment_tree_new_monoid_action;
+/// use monoid::Monoid;
+/// use monoid_action::MonoidAction;
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// struct Max(i64);
+/// impl Monoid for Max {
+///     fn op(&self, other: &Self) -> Self {
+///         Max(self.0.max(other.0))
+///     }
+///     fn id() -> Self {
+///         Max(i64::MIN)
+///     }
+/// }
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// struct Add(i64);
+/// impl Monoid for Add {
+///     fn op(&self, other: &Self) -> Self {
+///         Add(self.0 + other.0)
+///     }
+///     fn id() -> Self {
+///         Add(0)
+///     }
+/// }
+///
+/// struct AddMax;
+/// impl MonoidAction for AddMax {
+///     type T = Max;
+///     type A = Add;
+///     fn act_app(a: &Add, x: &Max) -> Max {
+///         Max(x.0.saturating_add(a.0))
+///     }
+/// }
+///
+/// let mut seg = lazy_segment_tree_new_monoid_action::<AddMax>(
+///     [1, 4, 2, 3, 8, 3, 4].into_iter().map(Max).collect(),
+/// );
+/// assert_eq!(seg.fold(1..5), Max(8));
+/// seg.act(..3, Add(5));
+/// assert_eq!(seg.fold(1..4), Max(9));
+/// assert_eq!(seg.get(3), Max(3));
+/// ```
+#[inline]
+pub fn lazy_segment_tree_new_monoid_action<M>(
+    vec: Vec<M::T>,
+) -> LazySegmentTreeStatic<M::T, M::T, M::T, M::T, M::A>
+where
+    M: MonoidAction,
+{
+    let monoid_action = M::as_quick();
+    lazy_segment_tree_new_static(
+        vec,
+        monoid_action.op,
+        monoid_action.id,
+        monoid_action.act_op,
+        monoid_action.act_id,
+        monoid_action.act_app,
+    )
+}
+
 #[cfg(test)]
 mod lazy_segment_tree_test;
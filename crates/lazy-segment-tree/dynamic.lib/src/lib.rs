@@ -0,0 +1,251 @@
+use int::Int;
+use std::ops::{Bound, RangeBounds};
+
+/// # 動的遅延セグメントツリー
+///
+/// 添字の範囲 `lo..hi` が非常に大きい場合に、必要なノードだけを確保する遅延セグメントツリー。
+/// 操作ごとに $O(\log (hi - lo))$ 個のノードが確保される。
+///
+/// 各ノードの区間の長さが `act_app_with_len` に渡されるため、区間和などへの作用も表せる。
+/// 一度も触れられていない長さ `len` の区間の畳み込みは `init_with_len(len)` で与える。
+pub struct DynamicLazySegmentTree<I, T, A, InitWithLen, Op, Id, ActOp, ActId, ActAppWithLen>
+where
+    I: Int,
+    T: Clone,
+    InitWithLen: Fn(I) -> T,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+    ActOp: Fn(&A, &A) -> A,
+    ActId: Fn() -> A,
+    ActAppWithLen: Fn(&A, &T, I) -> T,
+{
+    lo: I,
+    hi: I,
+    tree: Vec<T>,
+    lazy: Vec<A>,
+    /// 子がまだ確保されていなければ 0 (根は誰の子にもならない)
+    children: Vec<(usize, usize)>,
+
+    init_with_len: InitWithLen,
+    op: Op,
+    id: Id,
+    act_op: ActOp,
+    act_id: ActId,
+    act_app_with_len: ActAppWithLen,
+}
+
+impl<I, T, A, InitWithLen, Op, Id, ActOp, ActId, ActAppWithLen>
+    DynamicLazySegmentTree<I, T, A, InitWithLen, Op, Id, ActOp, ActId, ActAppWithLen>
+where
+    I: Int,
+    T: Clone,
+    InitWithLen: Fn(I) -> T,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+    ActOp: Fn(&A, &A) -> A,
+    ActId: Fn() -> A,
+    ActAppWithLen: Fn(&A, &T, I) -> T,
+{
+    /// 添字の範囲
+    #[inline]
+    pub fn index_range(&self) -> (I, I) {
+        (self.lo, self.hi)
+    }
+
+    /// これまでに確保されたノードの数
+    #[inline]
+    pub fn node_count(&self) -> usize {
+        self.tree.len()
+    }
+
+    /// `hi` 以上の端点は `hi` に丸めるので、 `I::MAX` を含む区間でもオーバーフローしない
+    fn to_half_open(&self, range: impl RangeBounds<I>) -> (I, I) {
+        let l = match range.start_bound() {
+            Bound::Included(&l) => l,
+            Bound::Excluded(&l) if l < self.hi => l + I::one(),
+            Bound::Excluded(_) => self.hi,
+            Bound::Unbounded => self.lo,
+        };
+        let r = match range.end_bound() {
+            Bound::Included(&r) if r < self.hi => r + I::one(),
+            Bound::Included(_) => self.hi,
+            Bound::Excluded(&r) => r,
+            Bound::Unbounded => self.hi,
+        };
+        (l.max(self.lo), r.min(self.hi))
+    }
+
+    #[inline]
+    fn new_node(&mut self, len: I) -> usize {
+        self.tree.push((self.init_with_len)(len));
+        self.lazy.push((self.act_id)());
+        self.children.push((0, 0));
+        self.tree.len() - 1
+    }
+
+    #[inline]
+    fn apply(&mut self, node: usize, a: &A, len: I) {
+        self.tree[node] = (self.act_app_with_len)(a, &self.tree[node], len);
+        self.lazy[node] = (self.act_op)(a, &self.lazy[node]);
+    }
+
+    /// 子を (必要なら確保して) 返し、作用を子に伝播する
+    fn push(&mut self, node: usize, nl: I, nm: I, nr: I) -> (usize, usize) {
+        if self.children[node].0 == 0 {
+            let left = self.new_node(nm - nl);
+            let right = self.new_node(nr - nm);
+            self.children[node] = (left, right);
+        }
+        let (left, right) = self.children[node];
+        let a = std::mem::replace(&mut self.lazy[node], (self.act_id)());
+        self.apply(left, &a, nm - nl);
+        self.apply(right, &a, nr - nm);
+        (left, right)
+    }
+
+    #[inline]
+    fn mid(nl: I, nr: I) -> I {
+        nl + ((nr - nl) >> 1)
+    }
+
+    fn act_inner(&mut self, node: usize, nl: I, nr: I, l: I, r: I, a: &A) {
+        if r <= nl || nr <= l {
+            return;
+        }
+        if l <= nl && nr <= r {
+            self.apply(node, a, nr - nl);
+            return;
+        }
+        let nm = Self::mid(nl, nr);
+        let (left, right) = self.push(node, nl, nm, nr);
+        self.act_inner(left, nl, nm, l, r, a);
+        self.act_inner(right, nm, nr, l, r, a);
+        self.tree[node] = (self.op)(&self.tree[left], &self.tree[right]);
+    }
+
+    fn fold_inner(&mut self, node: usize, nl: I, nr: I, l: I, r: I) -> T {
+        if r <= nl || nr <= l {
+            return (self.id)();
+        }
+        if l <= nl && nr <= r {
+            return self.tree[node].clone();
+        }
+        let nm = Self::mid(nl, nr);
+        let (left, right) = self.push(node, nl, nm, nr);
+        let left = self.fold_inner(left, nl, nm, l, r);
+        let right = self.fold_inner(right, nm, nr, l, r);
+        (self.op)(&left, &right)
+    }
+
+    /// # 区間への作用
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log (hi - lo))$
+    pub fn act(&mut self, range: impl RangeBounds<I>, a: A) {
+        let (l, r) = self.to_half_open(range);
+        if l >= r {
+            return;
+        }
+        self.act_inner(0, self.lo, self.hi, l, r, &a);
+    }
+
+    /// # 区間の畳み込み
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log (hi - lo))$
+    pub fn fold(&mut self, range: impl RangeBounds<I>) -> T {
+        let (l, r) = self.to_half_open(range);
+        if l >= r {
+            return (self.id)();
+        }
+        self.fold_inner(0, self.lo, self.hi, l, r)
+    }
+
+    /// # 取得
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log (hi - lo))$
+    #[inline]
+    pub fn get(&mut self, index: I) -> T {
+        assert!(
+            self.lo <= index && index < self.hi,
+            "index out of range: {}",
+            index
+        );
+        self.fold(index..=index)
+    }
+}
+
+/// # 動的遅延セグメントツリーの構築
+///
+/// 添字の範囲 `range` 、長さ `len` の初期区間の畳み込み、モノイド、作用の順で指定する。
+/// `range.end - range.start` は `I` で表せなければならない。
+///
+/// ## 計算量
+///
+/// $O(1)$
+///
+/// ## 例
+/// ```
+/// use lazy_segment_tree_dynamic::dynamic_lazy_segment_tree_new;
+/// // 区間加算・区間和 (初期値はすべて 1)
+/// let mut seg = dynamic_lazy_segment_tree_new(
+///     -1_000_000_000_000_000_000_i64..1_000_000_000_000_000_000,
+///     |len| len,
+///     |a, b| a + b,
+///     || 0,
+///     |a, b| a + b,
+///     || 0,
+///     |a, x, len| x + a * len,
+/// );
+/// seg.act(-5..5, 3);
+/// seg.act(0.., 1);
+/// assert_eq!(seg.fold(-10..10), 20 + 30 + 10);
+/// assert_eq!(seg.get(4), 5);
+/// assert_eq!(seg.get(-4), 4);
+/// assert_eq!(seg.get(999_999_999_999_999_999), 2);
+/// assert_eq!(seg.fold(..), 2_000_000_000_000_000_000 + 30 + 1_000_000_000_000_000_000);
+/// ```
+pub fn dynamic_lazy_segment_tree_new<I, T, A, InitWithLen, Op, Id, ActOp, ActId, ActAppWithLen>(
+    range: std::ops::Range<I>,
+    init_with_len: InitWithLen,
+    op: Op,
+    id: Id,
+    act_op: ActOp,
+    act_id: ActId,
+    act_app_with_len: ActAppWithLen,
+) -> DynamicLazySegmentTree<I, T, A, InitWithLen, Op, Id, ActOp, ActId, ActAppWithLen>
+where
+    I: Int,
+    T: Clone,
+    InitWithLen: Fn(I) -> T,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+    ActOp: Fn(&A, &A) -> A,
+    ActId: Fn() -> A,
+    ActAppWithLen: Fn(&A, &T, I) -> T,
+{
+    assert!(range.start < range.end, "range should not be empty");
+    let mut seg = DynamicLazySegmentTree {
+        lo: range.start,
+        hi: range.end,
+        tree: Vec::new(),
+        lazy: Vec::new(),
+        children: Vec::new(),
+
+        init_with_len,
+        op,
+        id,
+        act_op,
+        act_id,
+        act_app_with_len,
+    };
+    seg.new_node(range.end - range.start);
+    seg
+}
+
+#[cfg(test)]
+mod test;
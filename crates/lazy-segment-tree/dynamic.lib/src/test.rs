@@ -0,0 +1,90 @@
+use super::*;
+use rand::{self, Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+#[test]
+fn test_add_sum_random() {
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    for _ in 0..20 {
+        let lo = rng.gen_range(-50..50_i64);
+        let hi = lo + rng.gen_range(1..50);
+        let mut v = vec![0_i64; (hi - lo) as usize];
+        let mut seg = dynamic_lazy_segment_tree_new(
+            lo..hi,
+            |_| 0,
+            |a, b| a + b,
+            || 0,
+            |a, b| a + b,
+            || 0,
+            |a, x, len| x + a * len,
+        );
+        for _ in 0..100 {
+            let l = rng.gen_range(lo..=hi);
+            let r = rng.gen_range(l..=hi);
+            let a = rng.gen_range(-100..100);
+            seg.act(l..r, a);
+            for e in &mut v[(l - lo) as usize..(r - lo) as usize] {
+                *e += a;
+            }
+
+            let l = rng.gen_range(lo..=hi);
+            let r = rng.gen_range(l..=hi);
+            assert_eq!(
+                seg.fold(l..r),
+                v[(l - lo) as usize..(r - lo) as usize].iter().sum::<i64>()
+            );
+            let i = rng.gen_range(lo..hi);
+            assert_eq!(seg.get(i), v[(i - lo) as usize]);
+        }
+    }
+}
+
+#[test]
+fn test_affine_min_large() {
+    // 区間へのアフィン変換 (非可換) と区間最小
+    let mut seg = dynamic_lazy_segment_tree_new(
+        0_u64..1 << 60,
+        |_| 5_i64,
+        |a: &i64, b: &i64| *a.min(b),
+        || i64::MAX,
+        |f: &(i64, i64), g: &(i64, i64)| (f.0 * g.0, f.0 * g.1 + f.1),
+        || (1, 0),
+        |f, x, _| if *x == i64::MAX { *x } else { f.0 * x + f.1 },
+    );
+    seg.act(10..1 << 59, (2, 0));
+    seg.act(..100, (1, -3));
+    assert_eq!(seg.get(0), 2);
+    assert_eq!(seg.get(10), 7);
+    assert_eq!(seg.get(100), 10);
+    assert_eq!(seg.fold(..), 2);
+    assert_eq!(seg.fold(100..), 5);
+    assert_eq!(seg.fold(50..=50), 7);
+    assert!(seg.node_count() < 1000);
+}
+
+#[test]
+fn test_range_up_to_max() {
+    // 添字の上端が型の最大値でも、閉区間・開始側の開区間が扱える
+    let mut seg = dynamic_lazy_segment_tree_new(
+        i32::MAX - 10..i32::MAX,
+        |len| len as i64,
+        |a: &i64, b: &i64| a + b,
+        || 0,
+        |a: &i64, b: &i64| a + b,
+        || 0,
+        |a, x, len| x + a * len as i64,
+    );
+    assert_eq!(seg.fold(..=i32::MAX), 10);
+    assert_eq!(seg.fold(i32::MAX - 3..=i32::MAX), 3);
+    assert_eq!(
+        seg.fold((Bound::Excluded(i32::MAX), Bound::Included(i32::MAX))),
+        0
+    );
+    seg.act(i32::MAX - 1..=i32::MAX, 5);
+    assert_eq!(seg.fold(..), 15);
+    assert_eq!(seg.get(i32::MAX - 1), 6);
+    assert_eq!(
+        seg.fold((Bound::Excluded(i32::MAX - 2), Bound::Unbounded)),
+        6
+    );
+}
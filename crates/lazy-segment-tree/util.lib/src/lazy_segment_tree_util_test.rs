@@ -15,3 +15,350 @@ fn test_simple_set_sum() {
     seg.act(.., 1);
     assert_eq!(seg.fold(0), 1);
 }
+
+#[test]
+fn test_static_in_struct_field() {
+    use crate::{lazy_segment_tree_new_add_sum, lazy_segment_tree_new_set_min};
+    use lazy_segment_tree::LazySegmentTreeStatic;
+
+    struct Solver {
+        add_sum: LazySegmentTreeStatic<(i64, usize), i64, i64, i64, i64>,
+        set_min: LazySegmentTreeStatic<i32, i32, i32, i32, Option<i32>, i32>,
+    }
+
+    let mut solver = Solver {
+        add_sum: lazy_segment_tree_new_add_sum(vec![1, 4, 2, 3]),
+        set_min: lazy_segment_tree_new_set_min(vec![1, 4, 2, 3]),
+    };
+    solver.add_sum.act(1..3, 10);
+    assert_eq!(solver.add_sum.fold(..), 30);
+    assert_eq!(solver.add_sum.get(2), 12);
+    solver.add_sum.set(2, 0);
+    assert_eq!(solver.add_sum.fold(1..), 17);
+
+    solver.set_min.act(..2, 5);
+    assert_eq!(solver.set_min.fold(..), 2);
+    assert_eq!(solver.set_min.fold(..2), 5);
+}
+
+#[test]
+fn test_dyn_in_struct_field() {
+    use crate::lazy_segment_tree_builder_add_sum;
+    use lazy_segment_tree::LazySegmentTreeDyn;
+
+    struct Solver {
+        add_sum: LazySegmentTreeDyn<(i64, usize), i64, i64, i64, i64>,
+    }
+
+    let modulo = 7;
+    let b = lazy_segment_tree_builder_add_sum(vec![1_i64, 4, 2, 3])
+        .set_add(move |a, b| (a + b) % modulo)
+        .set_zero(|| 0)
+        .set_mul_usize(move |x, len| x * len as i64 % modulo);
+    let mut solver = Solver {
+        add_sum: b.build().into_dyn(),
+    };
+    assert_eq!(solver.add_sum.fold(..), 3);
+    solver.add_sum.act(1..3, 3);
+    assert_eq!(solver.add_sum.fold(..), 2);
+    assert_eq!(solver.add_sum.get(2), 5);
+}
+
+#[test]
+fn test_affine_sum() {
+    use crate::lazy_segment_tree_new_affine_sum;
+
+    let mut seg = lazy_segment_tree_new_affine_sum(vec![3_i64, -2, 5, 1]);
+    assert_eq!(seg.fold(..), 7);
+    // x -> 2x + 1 を適用してから x -> -x + 4 を適用する
+    seg.act(1..3, (2, 1));
+    seg.act(..2, (-1, 4));
+    assert_eq!(seg.get(0), 1);
+    assert_eq!(seg.get(1), 7);
+    assert_eq!(seg.get(2), 11);
+    assert_eq!(seg.fold(..), 1 + 7 + 11 + 1);
+    seg.set(2, 2);
+    seg.act(.., (3, 0));
+    assert_eq!(seg.fold(1..), 3 * (7 + 2 + 1));
+}
+
+#[test]
+fn test_affine_sum_shrinkable() {
+    use crate::lazy_segment_tree_new_affine_sum_shrinkable;
+    use shrink::shrink;
+
+    // 0..=10 をすべて 1 で埋めたものを、 0 と 10 で座標圧縮する
+    let sh = shrink::<u64, i64>(vec![0, 10]);
+    let mut seg = lazy_segment_tree_new_affine_sum_shrinkable(vec![1_i64, 9, 1], sh.clone());
+    assert_eq!(seg.fold(..), 11);
+    seg.act(sh.shrink(0..10), (2, 3));
+    assert_eq!(seg.fold(..), 5 * 10 + 1);
+    seg.act(sh.shrink(10), (0, -1));
+    assert_eq!(seg.fold(..), 5 * 10 - 1);
+    seg.act(.., (-1, 0));
+    assert_eq!(seg.fold(sh.shrink(1..=10)), -(5 * 9 - 1));
+}
+
+#[test]
+fn test_arithmetic_add_sum_shrinkable() {
+    use crate::{arithmetic_add, lazy_segment_tree_new_arithmetic_add_sum_shrinkable};
+    use shrink::shrink;
+
+    let points = [0_i64, 3, 7, 8, 15, 22, 30];
+    let sh = shrink::<u64, i64>(points.to_vec());
+    let mut seg = lazy_segment_tree_new_arithmetic_add_sum_shrinkable(
+        vec![0_i64; sh.shrinked_len()],
+        sh.clone(),
+    );
+    let mut v = vec![0_i64; 31];
+    for &(l, r, a, b) in &[(3, 8, 2, 3), (0, 30, -1, 1), (7, 22, 5, -2), (8, 15, 0, 4)] {
+        seg.act(sh.shrink(l..r), arithmetic_add(l, a, b));
+        for i in l..r {
+            v[i as usize] += a + b * (i - l);
+        }
+    }
+    for &l in &points {
+        for &r in &points {
+            if l < r {
+                assert_eq!(
+                    seg.fold(sh.shrink(l..r)),
+                    v[l as usize..r as usize].iter().sum::<i64>()
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_set_add_random() {
+    use crate::{
+        lazy_segment_tree_new_set_add_max_count, lazy_segment_tree_new_set_add_min_count,
+        lazy_segment_tree_new_set_add_sum, SetOrAdd,
+    };
+    use min_max_count::{MaxCount, MinCount};
+    use rand::{Rng, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    for n in 1..=20 {
+        let mut v = (0..n).map(|_| rng.gen_range(-5..5)).collect::<Vec<i64>>();
+        let mut sum = lazy_segment_tree_new_set_add_sum(v.clone());
+        let mut min = lazy_segment_tree_new_set_add_min_count(v.clone());
+        let mut max = lazy_segment_tree_new_set_add_max_count(v.clone());
+        for _ in 0..100 {
+            let l = rng.gen_range(0..=n);
+            let r = rng.gen_range(l..=n);
+            let x = rng.gen_range(-5..5);
+            let a = if rng.gen_bool(0.5) {
+                for e in &mut v[l..r] {
+                    *e = x;
+                }
+                SetOrAdd::Set(x)
+            } else {
+                for e in &mut v[l..r] {
+                    *e += x;
+                }
+                SetOrAdd::Add(x)
+            };
+            sum.act(l..r, a);
+            min.act(l..r, a);
+            max.act(l..r, a);
+
+            let l = rng.gen_range(0..n);
+            let r = rng.gen_range(l + 1..=n);
+            let s = &v[l..r];
+            assert_eq!(sum.fold(l..r), s.iter().sum::<i64>());
+            let m = *s.iter().min().unwrap();
+            assert_eq!(
+                min.fold(l..r),
+                MinCount {
+                    min: m,
+                    count: s.iter().filter(|&&e| e == m).count(),
+                }
+            );
+            let m = *s.iter().max().unwrap();
+            assert_eq!(
+                max.fold(l..r),
+                MaxCount {
+                    max: m,
+                    count: s.iter().filter(|&&e| e == m).count(),
+                }
+            );
+            assert_eq!(min.get(l), v[l]);
+        }
+    }
+}
+
+#[test]
+fn test_arg_min_max_random() {
+    use crate::{
+        lazy_segment_tree_builder_add_arg_max, lazy_segment_tree_builder_set_arg_min,
+        lazy_segment_tree_new_add_arg_min, lazy_segment_tree_new_set_arg_max,
+    };
+    use arg_min_max::{ArgMax, ArgMin, ArgPosition};
+    use rand::{Rng, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    fn arg_min(s: &[i64], l: usize, position: ArgPosition) -> ArgMin<i64, usize> {
+        let min = *s.iter().min().unwrap();
+        let index = match position {
+            ArgPosition::Leftmost => s.iter().position(|&e| e == min),
+            ArgPosition::Rightmost => s.iter().rposition(|&e| e == min),
+        };
+        ArgMin {
+            min,
+            index: l + index.unwrap(),
+        }
+    }
+    fn arg_max(s: &[i64], l: usize, position: ArgPosition) -> ArgMax<i64, usize> {
+        let max = *s.iter().max().unwrap();
+        let index = match position {
+            ArgPosition::Leftmost => s.iter().position(|&e| e == max),
+            ArgPosition::Rightmost => s.iter().rposition(|&e| e == max),
+        };
+        ArgMax {
+            max,
+            index: l + index.unwrap(),
+        }
+    }
+
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    for n in 1..=20 {
+        let v = (0..n).map(|_| rng.gen_range(-3..3)).collect::<Vec<i64>>();
+        let mut v_add = v.clone();
+        let mut v_set = v.clone();
+        let mut add_min = lazy_segment_tree_new_add_arg_min(v.clone());
+        let mut add_max = lazy_segment_tree_builder_add_arg_max(v.clone())
+            .set_all_auto()
+            .set_position(ArgPosition::Rightmost)
+            .build();
+        let mut set_min = lazy_segment_tree_builder_set_arg_min(v.clone())
+            .set_all_auto()
+            .set_position(ArgPosition::Rightmost)
+            .build();
+        let mut set_max = lazy_segment_tree_new_set_arg_max(v.clone());
+        for _ in 0..100 {
+            let l = rng.gen_range(0..=n);
+            let r = rng.gen_range(l..=n);
+            let x = rng.gen_range(-3..3);
+            for e in &mut v_add[l..r] {
+                *e += x;
+            }
+            add_min.act(l..r, x);
+            add_max.act(l..r, x);
+
+            let l = rng.gen_range(0..=n);
+            let r = rng.gen_range(l..=n);
+            let x = rng.gen_range(-3..3);
+            for e in &mut v_set[l..r] {
+                *e = x;
+            }
+            set_min.act(l..r, x);
+            set_max.act(l..r, x);
+
+            let l = rng.gen_range(0..n);
+            let r = rng.gen_range(l + 1..=n);
+            let s = &v_add[l..r];
+            assert_eq!(add_min.fold(l..r), arg_min(s, l, ArgPosition::Leftmost));
+            assert_eq!(add_max.fold(l..r), arg_max(s, l, ArgPosition::Rightmost));
+            let s = &v_set[l..r];
+            assert_eq!(set_min.fold(l..r), arg_min(s, l, ArgPosition::Rightmost));
+            assert_eq!(set_max.fold(l..r), arg_max(s, l, ArgPosition::Leftmost));
+            assert_eq!(add_min.get(l), v_add[l]);
+            assert_eq!(set_min.get(l), v_set[l]);
+        }
+    }
+}
+
+#[test]
+fn test_arg_min_max_shrinkable() {
+    use crate::{
+        lazy_segment_tree_builder_set_arg_max, lazy_segment_tree_new_add_arg_min_shrinkable,
+    };
+    use arg_min_max::{ArgMax, ArgMin, ArgPosition};
+    use shrink::shrink;
+
+    // 0, 1..=9, 10, 11..=19, 20, 21..=100
+    let sh = shrink::<u64, i64>(vec![0, 10, 20, 100]);
+    let mut seg =
+        lazy_segment_tree_new_add_arg_min_shrinkable(vec![0_i64; sh.shrinked_len()], sh.clone());
+    seg.act(sh.shrink(..=10), 1);
+    assert_eq!(seg.fold(..), ArgMin { min: 0, index: 11 });
+    seg.act(sh.shrink(11..=100), 2);
+    assert_eq!(seg.fold(..), ArgMin { min: 1, index: 0 });
+    assert_eq!(seg.fold(sh.shrink(20..=100)), ArgMin { min: 2, index: 20 });
+
+    let mut seg = lazy_segment_tree_builder_set_arg_max(vec![0_i64; sh.shrinked_len()])
+        .set_all_auto()
+        .set_position(ArgPosition::Rightmost)
+        .build_shrinkable(sh.clone());
+    assert_eq!(seg.fold(..), ArgMax { max: 0, index: 100 });
+    seg.act(sh.shrink(11..=19), 5);
+    assert_eq!(seg.fold(..), ArgMax { max: 5, index: 19 });
+    seg.act(sh.shrink(20..=20), 5);
+    assert_eq!(seg.fold(..), ArgMax { max: 5, index: 20 });
+}
+
+#[test]
+fn test_flip_runs_random() {
+    use crate::lazy_segment_tree_new_flip_runs;
+    use rand::{Rng, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    fn longest(s: &[bool], bit: bool) -> usize {
+        s.split(|&b| b != bit).map(|r| r.len()).max().unwrap()
+    }
+
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    for n in 1..=20 {
+        let mut v = (0..n).map(|_| rng.gen_bool(0.5)).collect::<Vec<bool>>();
+        let mut seg = lazy_segment_tree_new_flip_runs(v.clone());
+        for _ in 0..100 {
+            let l = rng.gen_range(0..=n);
+            let r = rng.gen_range(l..=n);
+            if rng.gen_bool(0.8) {
+                for e in &mut v[l..r] {
+                    *e = !*e;
+                }
+                seg.act(l..r, true);
+            } else if l < n {
+                let b = rng.gen_bool(0.5);
+                v[l] = b;
+                seg.set(l, b);
+            }
+
+            let l = rng.gen_range(0..=n);
+            let r = rng.gen_range(l..=n);
+            let s = &v[l..r];
+            let f = seg.fold(l..r);
+            assert_eq!(f.len, r - l);
+            assert_eq!(f.count_ones(), s.iter().filter(|&&b| b).count());
+            assert_eq!(f.longest_ones(), longest(s, true));
+            assert_eq!(f.longest_zeros(), longest(s, false));
+            assert_eq!(f.prefix[1], s.iter().take_while(|&&b| b).count());
+            assert_eq!(f.suffix[0], s.iter().rev().take_while(|&&b| !b).count());
+        }
+        for (i, &b) in v.iter().enumerate() {
+            assert_eq!(seg.get(i), b);
+        }
+    }
+}
+
+#[test]
+fn test_flip_runs_shrinkable() {
+    use crate::lazy_segment_tree_new_flip_runs_shrinkable;
+    use shrink::shrink;
+
+    // 0, 1..=9, 10, 11..=19
+    let sh = shrink::<u64, i64>(vec![0, 9, 10, 19]);
+    let mut seg =
+        lazy_segment_tree_new_flip_runs_shrinkable(vec![false; sh.shrinked_len()], sh.clone());
+    assert_eq!(seg.fold(..).longest_zeros(), 20);
+    seg.act(sh.shrink(1..=9), true);
+    assert_eq!(seg.fold(..).count_ones(), 9);
+    assert_eq!(seg.fold(..).longest_zeros(), 10);
+    seg.act(sh.shrink(..=10), true);
+    assert_eq!(seg.fold(..).count_ones(), 2);
+    assert_eq!(seg.fold(..).longest_ones(), 1);
+    assert_eq!(seg.fold(..).longest_zeros(), 9);
+}
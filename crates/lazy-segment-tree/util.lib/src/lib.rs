@@ -1,8 +1,13 @@
 pub use lazy_segment_tree_util_add_min_max::*;
 pub use lazy_segment_tree_util_add_min_max_count::*;
 pub use lazy_segment_tree_util_add_sum::*;
+pub use lazy_segment_tree_util_affine_sum::*;
+pub use lazy_segment_tree_util_arg_min_max::*;
+pub use lazy_segment_tree_util_arithmetic_add_sum::*;
+pub use lazy_segment_tree_util_flip_runs::*;
 pub use lazy_segment_tree_util_new_with_len::*;
 pub use lazy_segment_tree_util_new_with_range::*;
+pub use lazy_segment_tree_util_set_add::*;
 pub use lazy_segment_tree_util_set_min_max::*;
 pub use lazy_segment_tree_util_set_min_max_count::*;
 pub use lazy_segment_tree_util_set_sum::*;
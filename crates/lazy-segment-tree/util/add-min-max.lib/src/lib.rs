@@ -1,5 +1,7 @@
 use commutative_ring_ord::CommutativeRingOrd;
-use lazy_segment_tree::{lazy_segment_tree_new, LazySegmentTree};
+use lazy_segment_tree::{
+    lazy_segment_tree_new, lazy_segment_tree_new_static, LazySegmentTree, LazySegmentTreeStatic,
+};
 use lazy_segment_tree_util_type::lazy_seg_type;
 use max_exists::MaxExists;
 use min_exists::MinExists;
@@ -192,12 +194,25 @@ macro_rules! f {
         }
 
         #[doc = include_str!($doc_fn_new)]
-        pub fn $fn_new<T>(vec: Vec<T>) -> lazy_seg_type!(T = T)
+        pub fn $fn_new<T>(vec: Vec<T>) -> LazySegmentTreeStatic<T>
         where
             T: Clone + CommutativeRingOrd + cmp::PartialOrd + $max_or_min_exists,
         {
-            let b = $fn_builder(vec).set_all_auto();
-            unsafe { b.build_unchecked() }
+            fn op<T: Clone + cmp::PartialOrd>(a: &T, b: &T) -> T {
+                match a.partial_cmp(b) {
+                    Some(cmp::Ordering::$greater_or_less | cmp::Ordering::Equal) => b.clone(),
+                    Some(cmp::Ordering::$less_or_greater) => a.clone(),
+                    None => panic!("{}: partial_cmp should be total", stringify!($builder_name)),
+                }
+            }
+            lazy_segment_tree_new_static(
+                vec,
+                op::<T>,
+                T::$max_or_min_exists_method,
+                T::add,
+                T::zero,
+                T::add,
+            )
         }
     };
 }
@@ -0,0 +1,235 @@
+use commutative_ring::CommutativeRing;
+use lazy_segment_tree::LazySegmentTree;
+use lazy_segment_tree_util_new_with_len::lazy_segment_tree_new_with_len_shrinkable;
+use lazy_segment_tree_util_type::lazy_seg_type;
+use shrink_provider::{NoShrink, ShrinkProvider};
+use std::ops;
+use std::rc::Rc;
+
+pub struct LazySegmentTreeAffineSum<T, SP>
+where
+    SP: ShrinkProvider + Clone,
+{
+    vec: Vec<T>,
+    t_add: Option<Box<dyn Fn(&T, &T) -> T>>,
+    t_zero: Option<Box<dyn Fn() -> T>>,
+    t_mul: Option<Box<dyn Fn(&T, &T) -> T>>,
+    t_one: Option<Box<dyn Fn() -> T>>,
+    t_mul_usize: Option<Box<dyn Fn(&T, SP::USize) -> T>>,
+    sp: SP,
+}
+
+impl<T, SP> LazySegmentTreeAffineSum<T, SP>
+where
+    SP: ShrinkProvider + Clone,
+{
+    pub fn new(vec: Vec<T>, sp: SP) -> Self {
+        Self {
+            vec,
+            t_add: None,
+            t_zero: None,
+            t_mul: None,
+            t_one: None,
+            t_mul_usize: None,
+            sp,
+        }
+    }
+
+    pub fn set_add(mut self, t_add: impl Fn(&T, &T) -> T + 'static) -> Self {
+        self.t_add = Some(Box::new(t_add));
+        self
+    }
+    pub fn set_add_zero_by_commutative_ring_add(self) -> Self
+    where
+        T: CommutativeRing,
+    {
+        self.set_add(|a, b| a.add(b)).set_zero(|| T::zero())
+    }
+    pub fn set_add_by_add(self) -> Self
+    where
+        T: Clone + ops::Add<Output = T>,
+    {
+        self.set_add(|a, b| a.clone() + b.clone())
+    }
+
+    pub fn set_zero(mut self, t_zero: impl Fn() -> T + 'static) -> Self {
+        self.t_zero = Some(Box::new(t_zero));
+        self
+    }
+    pub fn set_zero_by_default(self) -> Self
+    where
+        T: Default,
+    {
+        self.set_zero(|| T::default())
+    }
+
+    pub fn set_mul(mut self, t_mul: impl Fn(&T, &T) -> T + 'static) -> Self {
+        self.t_mul = Some(Box::new(t_mul));
+        self
+    }
+    pub fn set_mul_one_by_commutative_ring_mul(self) -> Self
+    where
+        T: CommutativeRing,
+    {
+        self.set_mul(|a, b| a.mul(b)).set_one(|| T::one())
+    }
+    pub fn set_mul_by_mul(self) -> Self
+    where
+        T: Clone + ops::Mul<Output = T>,
+    {
+        self.set_mul(|a, b| a.clone() * b.clone())
+    }
+
+    pub fn set_one(mut self, t_one: impl Fn() -> T + 'static) -> Self {
+        self.t_one = Some(Box::new(t_one));
+        self
+    }
+
+    pub fn set_mul_usize(mut self, t_mul_usize: impl Fn(&T, SP::USize) -> T + 'static) -> Self {
+        self.t_mul_usize = Some(Box::new(t_mul_usize));
+        self
+    }
+    pub fn set_mul_usize_auto(self) -> Self
+    where
+        T: Clone + TryFrom<SP::USize> + ops::Mul<Output = T>,
+    {
+        self.set_mul_usize_by_mul()
+    }
+    pub fn set_mul_usize_by_mul(self) -> Self
+    where
+        T: Clone + TryFrom<SP::USize> + ops::Mul<Output = T>,
+    {
+        self.set_mul_usize(|x, len| {
+            let len = len.try_into().unwrap_or_else(|_| {
+                panic!(
+                    "{}: Couldn't convert USize {} to T",
+                    stringify!(LazySegmentTreeAffineSum),
+                    len,
+                )
+            });
+            x.clone() * len
+        })
+    }
+
+    pub fn build(
+        self,
+    ) -> lazy_seg_type!(
+        T = (T, SP::USize),
+        TFolded = T,
+        TGetter = T,
+        TSetter = T,
+        A = (T, T),
+    ) {
+        self.t_add
+            .as_ref()
+            .or_else(|| panic!("{}: add is not set", stringify!(LazySegmentTreeAffineSum)));
+        self.t_zero
+            .as_ref()
+            .or_else(|| panic!("{}: zero is not set", stringify!(LazySegmentTreeAffineSum)));
+        self.t_mul
+            .as_ref()
+            .or_else(|| panic!("{}: mul is not set", stringify!(LazySegmentTreeAffineSum)));
+        self.t_one
+            .as_ref()
+            .or_else(|| panic!("{}: one is not set", stringify!(LazySegmentTreeAffineSum)));
+        self.t_mul_usize.as_ref().or_else(|| {
+            panic!(
+                "{}: mul_usize is not set",
+                stringify!(LazySegmentTreeAffineSum)
+            )
+        });
+        unsafe { self.build_unchecked() }
+    }
+
+    /// ## Safety
+    /// - すべてのメソッドが設定されていること
+    pub unsafe fn build_unchecked(
+        self,
+    ) -> lazy_seg_type!(
+        T = (T, SP::USize),
+        TFolded = T,
+        TGetter = T,
+        TSetter = T,
+        A = (T, T),
+    ) {
+        let t_add = Rc::new(unsafe { self.t_add.unwrap_unchecked() });
+        let t_zero = Rc::new(unsafe { self.t_zero.unwrap_unchecked() });
+        let t_mul = Rc::new(unsafe { self.t_mul.unwrap_unchecked() });
+        let t_one = unsafe { self.t_one.unwrap_unchecked() };
+        let t_mul_usize = unsafe { self.t_mul_usize.unwrap_unchecked() };
+        lazy_segment_tree_new_with_len_shrinkable(
+            self.vec,
+            {
+                let t_add = t_add.clone();
+                move |a, b| t_add(a, b)
+            },
+            {
+                let t_zero = t_zero.clone();
+                move || t_zero()
+            },
+            {
+                let t_add = t_add.clone();
+                let t_mul = t_mul.clone();
+                // (a, b) の後に (c, d) を作用させると c * (a * x + b) + d
+                move |(c, d): &(T, T), (a, b): &(T, T)| (t_mul(c, a), t_add(&t_mul(c, b), d))
+            },
+            move || (t_one(), t_zero()),
+            move |(a, b), x, len| t_add(&t_mul(a, x), &t_mul_usize(b, len)),
+            self.sp,
+        )
+    }
+}
+
+pub fn lazy_segment_tree_builder_affine_sum_shrinkable<T, SP>(
+    vec: Vec<T>,
+    sp: SP,
+) -> LazySegmentTreeAffineSum<T, SP>
+where
+    SP: ShrinkProvider + Clone,
+{
+    LazySegmentTreeAffineSum::new(vec, sp)
+}
+
+pub fn lazy_segment_tree_builder_affine_sum<T>(vec: Vec<T>) -> LazySegmentTreeAffineSum<T, NoShrink>
+where
+    T: Clone,
+{
+    LazySegmentTreeAffineSum::new(vec, NoShrink)
+}
+
+pub fn lazy_segment_tree_new_affine_sum_shrinkable<T, SP>(
+    vec: Vec<T>,
+    sp: SP,
+) -> lazy_seg_type!(
+    T = (T, SP::USize),
+    TFolded = T,
+    TGetter = T,
+    TSetter = T,
+    A = (T, T),
+)
+where
+    T: Clone + CommutativeRing + TryFrom<SP::USize> + ops::Mul<Output = T>,
+    SP: ShrinkProvider + Clone,
+{
+    let b = lazy_segment_tree_builder_affine_sum_shrinkable(vec, sp)
+        .set_add_zero_by_commutative_ring_add()
+        .set_mul_one_by_commutative_ring_mul()
+        .set_mul_usize_by_mul();
+    unsafe { b.build_unchecked() }
+}
+
+#[doc = include_str!("../doc_new_affine_sum.md")]
+pub fn lazy_segment_tree_new_affine_sum<T>(
+    vec: Vec<T>,
+) -> lazy_seg_type!(
+    T = (T, usize),
+    TFolded = T,
+    TGetter = T,
+    TSetter = T,
+    A = (T, T),
+)
+where
+    T: Clone + CommutativeRing + TryFrom<usize> + ops::Mul<Output = T>,
+{
+    lazy_segment_tree_new_affine_sum_shrinkable(vec, NoShrink)
+}
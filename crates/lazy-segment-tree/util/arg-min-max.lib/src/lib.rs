@@ -0,0 +1,602 @@
+use arg_min_max::{ArgMax, ArgMin, ArgPosition};
+use commutative_ring_ord::CommutativeRingOrd;
+use int::{Int, UnsignedInt};
+use lazy_segment_tree::{lazy_segment_tree_new, LazySegmentTree};
+use lazy_segment_tree_util_type::lazy_seg_type;
+use max_exists::MaxExists;
+use min_exists::MinExists;
+use shrink::Shrink;
+use std::cmp;
+use std::ops;
+use std::rc::Rc;
+
+/// 各ノードは (値, 値を取る位置, 区間の左端, 区間の右端) を持つ
+/// 単位元は区間の左端が右端より大きく、作用を受けない
+/// `act_app(a, x, i, first, last)` は作用後の (値, 値を取る位置) を返す
+fn lazy_segment_tree_new_arg<T, A, ActOp, ActId, ActApp>(
+    vec: Vec<T>,
+    t_ord: impl Fn(&T, &T) -> cmp::Ordering,
+    t_worst: impl Fn() -> T,
+    position: ArgPosition,
+    act_op: ActOp,
+    act_id: ActId,
+    act_app: ActApp,
+) -> lazy_seg_type!(
+    T = (T, usize, usize, usize),
+    TFolded = (T, usize),
+    TGetter = T,
+    TSetter = T,
+    A = A,
+)
+where
+    T: Clone,
+    ActOp: Fn(&A, &A) -> A,
+    ActId: Fn() -> A,
+    ActApp: Fn(&A, &T, usize, usize, usize) -> (T, usize),
+{
+    lazy_segment_tree_new(
+        vec.into_iter()
+            .enumerate()
+            .map(|(i, x)| (x, i, i, i))
+            .collect(),
+        move |a: &(T, usize, usize, usize), b: &(T, usize, usize, usize)| {
+            let (x, i) = match t_ord(&a.0, &b.0) {
+                cmp::Ordering::Less => (a.0.clone(), a.1),
+                cmp::Ordering::Greater => (b.0.clone(), b.1),
+                cmp::Ordering::Equal => (a.0.clone(), position.select(a.1, b.1)),
+            };
+            (x, i, a.2.min(b.2), a.3.max(b.3))
+        },
+        move || (t_worst(), usize::MAX, usize::MAX, usize::MIN),
+        act_op,
+        act_id,
+        move |a, t: &(T, usize, usize, usize)| {
+            if t.2 > t.3 {
+                return t.clone();
+            }
+            let (x, i) = act_app(a, &t.0, t.1, t.2, t.3);
+            (x, i, t.2, t.3)
+        },
+    )
+    .set_value_folded(|(x, i, _, _)| (x, i))
+    .set_value_getter(|(x, _, _, _), _| x)
+    .set_value_setter(|x, i| (x, i, i, i))
+}
+
+/// 圧縮後の位置 `i` を圧縮前の座標に戻す
+/// 空の区間に対する位置 (`usize::MAX`) は `Index::max_exists()` とする
+fn unshrink_position<USize, Index>(
+    shrink: &Shrink<USize, Index>,
+    position: ArgPosition,
+    i: usize,
+) -> Index
+where
+    USize: UnsignedInt,
+    Index: Int<UnsignedIntSameSize = USize>,
+{
+    if i == usize::MAX {
+        return Index::max_exists();
+    }
+    let range = shrink.unshrink(i).unwrap_range_inclusive();
+    match position {
+        ArgPosition::Leftmost => *range.start(),
+        ArgPosition::Rightmost => *range.end(),
+    }
+}
+
+/// 加算・代入のビルダーに共通する、順序・単位元・位置の設定と構築、および構築関数
+///
+/// ビルダーの側で `check` と `build_inner` を定義しておく。
+macro_rules! impl_shared {
+    (
+        $builder_name:ident,
+        $fn_new:ident,
+        $fn_new_shrinkable:ident,
+        $fn_builder:ident,
+        $min_or_max:ident,
+        $arg_min_or_max:ident,
+        $max_or_min_exists:ident,
+        $max_or_min_exists_method:ident,
+        $set_max_or_min_exists:ident,
+        $set_max_or_min_exists_auto:ident,
+        $less_or_greater:ident,
+        A = $a:ty,
+        ASetter = $a_setter:ty,
+        T: [$($t_bound:tt)*],
+        $doc_fn_new:expr $(,)?
+    ) => {
+        impl<T> $builder_name<T>
+        where
+            T: Clone,
+        {
+            pub fn set_ord(mut self, t_ord: impl Fn(&T, &T) -> cmp::Ordering + 'static) -> Self {
+                self.t_ord = Some(Box::new(t_ord));
+                self
+            }
+            pub fn set_ord_auto(self) -> Self
+            where
+                T: cmp::PartialOrd,
+            {
+                self.set_ord_by_partial_ord()
+            }
+            pub fn set_ord_by_ord(self) -> Self
+            where
+                T: cmp::Ord,
+            {
+                self.set_ord(|a, b| a.cmp(b))
+            }
+            pub fn set_ord_by_partial_ord(self) -> Self
+            where
+                T: cmp::PartialOrd,
+            {
+                self.set_ord(|a, b| {
+                    a.partial_cmp(b).unwrap_or_else(|| {
+                        panic!("{}: partial_cmp should be total", stringify!($builder_name))
+                    })
+                })
+            }
+
+            pub fn $set_max_or_min_exists(
+                mut self,
+                t_max_or_min_exists: impl Fn() -> T + 'static,
+            ) -> Self {
+                self.t_max_or_min_exists = Some(Box::new(t_max_or_min_exists));
+                self
+            }
+            pub fn $set_max_or_min_exists_auto(self) -> Self
+            where
+                T: $max_or_min_exists,
+            {
+                self.$set_max_or_min_exists(|| T::$max_or_min_exists_method())
+            }
+
+            /// 値が等しい位置が複数あるときにどれを選ぶか (既定では最も左)
+            pub fn set_position(mut self, position: ArgPosition) -> Self {
+                self.position = position;
+                self
+            }
+
+            fn check_ord_and_exists(&self) {
+                self.t_max_or_min_exists.as_ref().or_else(|| {
+                    panic!(
+                        "{}: max_or_min_exists is not set",
+                        stringify!($builder_name)
+                    )
+                });
+                self.t_ord
+                    .as_ref()
+                    .or_else(|| panic!("{}: ord is not set", stringify!($builder_name)));
+            }
+
+            /// (求める値ほど小さくなる順序, 単位元) を取り出す
+            ///
+            /// ## Safety
+            /// - 順序と単位元が設定されていること
+            unsafe fn take_ord_and_exists(
+                &mut self,
+            ) -> (
+                impl Fn(&T, &T) -> cmp::Ordering,
+                Box<dyn Fn() -> T>,
+            ) {
+                let t_ord = unsafe { self.t_ord.take().unwrap_unchecked() };
+                let t_max_or_min_exists =
+                    unsafe { self.t_max_or_min_exists.take().unwrap_unchecked() };
+                (
+                    move |a: &T, b: &T| match t_ord(a, b) {
+                        cmp::Ordering::$less_or_greater => cmp::Ordering::Less,
+                        cmp::Ordering::Equal => cmp::Ordering::Equal,
+                        _ => cmp::Ordering::Greater,
+                    },
+                    t_max_or_min_exists,
+                )
+            }
+
+            pub fn build(
+                self,
+            ) -> lazy_seg_type!(
+                   T = (T, usize, usize, usize),
+                   TFolded = $arg_min_or_max<T, usize>,
+                   TGetter = T,
+                   TSetter = T,
+                   A = $a,
+                   ASetter = $a_setter,
+               ) {
+                self.check();
+                unsafe { self.build_unchecked() }
+            }
+            /// ## Safety
+            /// - すべてのメソッドが設定されていること
+            pub unsafe fn build_unchecked(
+                self,
+            ) -> lazy_seg_type!(
+                   T = (T, usize, usize, usize),
+                   TFolded = $arg_min_or_max<T, usize>,
+                   TGetter = T,
+                   TSetter = T,
+                   A = $a,
+                   ASetter = $a_setter,
+               ) {
+                self.build_inner()
+                    .map_value_folded(|(x, i)| $arg_min_or_max {
+                        $min_or_max: x,
+                        index: i,
+                    })
+            }
+
+            /// 位置を `shrink` によって圧縮される前の座標として返す
+            pub fn build_shrinkable<USize, Index>(
+                self,
+                shrink: Rc<Shrink<USize, Index>>,
+            ) -> lazy_seg_type!(
+                   T = (T, usize, usize, usize),
+                   TFolded = $arg_min_or_max<T, Index>,
+                   TGetter = T,
+                   TSetter = T,
+                   A = $a,
+                   ASetter = $a_setter,
+               )
+            where
+                USize: UnsignedInt,
+                Index: Int<UnsignedIntSameSize = USize>,
+            {
+                self.check();
+                unsafe { self.build_shrinkable_unchecked(shrink) }
+            }
+            /// ## Safety
+            /// - すべてのメソッドが設定されていること
+            pub unsafe fn build_shrinkable_unchecked<USize, Index>(
+                self,
+                shrink: Rc<Shrink<USize, Index>>,
+            ) -> lazy_seg_type!(
+                   T = (T, usize, usize, usize),
+                   TFolded = $arg_min_or_max<T, Index>,
+                   TGetter = T,
+                   TSetter = T,
+                   A = $a,
+                   ASetter = $a_setter,
+               )
+            where
+                USize: UnsignedInt,
+                Index: Int<UnsignedIntSameSize = USize>,
+            {
+                let position = self.position;
+                self.build_inner()
+                    .map_value_folded(move |(x, i)| $arg_min_or_max {
+                        $min_or_max: x,
+                        index: unshrink_position(&shrink, position, i),
+                    })
+            }
+        }
+
+        pub fn $fn_builder<T>(vec: Vec<T>) -> $builder_name<T>
+        where
+            T: Clone,
+        {
+            $builder_name::new(vec)
+        }
+
+        #[doc = include_str!($doc_fn_new)]
+        pub fn $fn_new<T>(
+            vec: Vec<T>,
+        ) -> lazy_seg_type!(
+               T = (T, usize, usize, usize),
+               TFolded = $arg_min_or_max<T, usize>,
+               TGetter = T,
+               TSetter = T,
+               A = $a,
+               ASetter = $a_setter,
+           )
+        where
+            T: $($t_bound)*,
+        {
+            $fn_builder(vec).set_all_auto().build()
+        }
+
+        /// 位置を `shrink` によって圧縮される前の座標として返す
+        /// 各要素は圧縮された区間全体に同じ値が並んでいるものとする
+        pub fn $fn_new_shrinkable<T, USize, Index>(
+            vec: Vec<T>,
+            shrink: Rc<Shrink<USize, Index>>,
+        ) -> lazy_seg_type!(
+               T = (T, usize, usize, usize),
+               TFolded = $arg_min_or_max<T, Index>,
+               TGetter = T,
+               TSetter = T,
+               A = $a,
+               ASetter = $a_setter,
+           )
+        where
+            T: $($t_bound)*,
+            USize: UnsignedInt,
+            Index: Int<UnsignedIntSameSize = USize>,
+        {
+            $fn_builder(vec).set_all_auto().build_shrinkable(shrink)
+        }
+    };
+}
+
+macro_rules! f {
+    (
+        $add_builder_name:ident,
+        $set_builder_name:ident,
+        $fn_add_new:ident,
+        $fn_add_new_shrinkable:ident,
+        $fn_add_builder:ident,
+        $fn_set_new:ident,
+        $fn_set_new_shrinkable:ident,
+        $fn_set_builder:ident,
+        $min_or_max:ident,
+        $arg_min_or_max:ident,
+        $max_or_min_exists:ident,
+        $max_or_min_exists_method:ident,
+        $set_max_or_min_exists:ident,
+        $set_max_or_min_exists_auto:ident,
+        $less_or_greater:ident,
+        $doc_fn_add_new:expr,
+        $doc_fn_set_new:expr $(,)?
+    ) => {
+        pub struct $add_builder_name<T>
+        where
+            T: Clone,
+        {
+            vec: Vec<T>,
+            t_add: Option<Box<dyn Fn(&T, &T) -> T>>,
+            t_zero: Option<Box<dyn Fn() -> T>>,
+            t_ord: Option<Box<dyn Fn(&T, &T) -> cmp::Ordering>>,
+            t_max_or_min_exists: Option<Box<dyn Fn() -> T>>,
+            position: ArgPosition,
+        }
+
+        impl_shared!(
+            $add_builder_name,
+            $fn_add_new,
+            $fn_add_new_shrinkable,
+            $fn_add_builder,
+            $min_or_max,
+            $arg_min_or_max,
+            $max_or_min_exists,
+            $max_or_min_exists_method,
+            $set_max_or_min_exists,
+            $set_max_or_min_exists_auto,
+            $less_or_greater,
+            A = T,
+            ASetter = T,
+            T: [Clone + CommutativeRingOrd + cmp::PartialOrd + $max_or_min_exists],
+            $doc_fn_add_new,
+        );
+
+        impl<T> $add_builder_name<T>
+        where
+            T: Clone,
+        {
+            pub fn new(vec: Vec<T>) -> Self {
+                Self {
+                    vec,
+                    t_add: None,
+                    t_zero: None,
+                    t_ord: None,
+                    t_max_or_min_exists: None,
+                    position: ArgPosition::Leftmost,
+                }
+            }
+
+            pub fn set_all_auto(self) -> Self
+            where
+                T: CommutativeRingOrd + cmp::PartialOrd + $max_or_min_exists,
+            {
+                self.set_add_auto()
+                    .set_zero_auto()
+                    .set_ord_auto()
+                    .$set_max_or_min_exists_auto()
+            }
+
+            pub fn set_add(mut self, t_add: impl Fn(&T, &T) -> T + 'static) -> Self {
+                self.t_add = Some(Box::new(t_add));
+                self
+            }
+            pub fn set_add_auto(self) -> Self
+            where
+                T: CommutativeRingOrd,
+            {
+                self.set_add_by_commutative_ring_ord()
+            }
+            pub fn set_add_by_commutative_ring_ord(self) -> Self
+            where
+                T: CommutativeRingOrd,
+            {
+                self.set_add(|a, b| a.add(b))
+            }
+            pub fn set_add_by_add(self) -> Self
+            where
+                T: ops::Add<Output = T>,
+            {
+                self.set_add(|a, b| a.clone() + b.clone())
+            }
+
+            pub fn set_zero(mut self, t_zero: impl Fn() -> T + 'static) -> Self {
+                self.t_zero = Some(Box::new(t_zero));
+                self
+            }
+            pub fn set_zero_auto(self) -> Self
+            where
+                T: CommutativeRingOrd,
+            {
+                self.set_zero_by_commutative_ring_ord()
+            }
+            pub fn set_zero_by_commutative_ring_ord(self) -> Self
+            where
+                T: CommutativeRingOrd,
+            {
+                self.set_zero(|| T::zero())
+            }
+            pub fn set_zero_by_default(self) -> Self
+            where
+                T: Default,
+            {
+                self.set_zero(|| T::default())
+            }
+
+            fn check(&self) {
+                self.t_add
+                    .as_ref()
+                    .or_else(|| panic!("{}: add is not set", stringify!($add_builder_name)));
+                self.t_zero
+                    .as_ref()
+                    .or_else(|| panic!("{}: zero is not set", stringify!($add_builder_name)));
+                self.check_ord_and_exists();
+            }
+
+            unsafe fn build_inner(
+                mut self,
+            ) -> lazy_seg_type!(
+                T = (T, usize, usize, usize),
+                TFolded = (T, usize),
+                TGetter = T,
+                TSetter = T,
+                A = T,
+            ) {
+                let (t_ord, t_max_or_min_exists) = unsafe { self.take_ord_and_exists() };
+                let t_add = Rc::new(unsafe { self.t_add.unwrap_unchecked() });
+                let t_zero = unsafe { self.t_zero.unwrap_unchecked() };
+                lazy_segment_tree_new_arg(
+                    self.vec,
+                    t_ord,
+                    t_max_or_min_exists,
+                    self.position,
+                    {
+                        let t_add = t_add.clone();
+                        move |x: &T, y: &T| t_add(x, y)
+                    },
+                    t_zero,
+                    // 加算では値を取る位置は変わらない
+                    move |a, x, i, _, _| (t_add(x, a), i),
+                )
+            }
+        }
+
+        pub struct $set_builder_name<T>
+        where
+            T: Clone,
+        {
+            vec: Vec<T>,
+            t_ord: Option<Box<dyn Fn(&T, &T) -> cmp::Ordering>>,
+            t_max_or_min_exists: Option<Box<dyn Fn() -> T>>,
+            position: ArgPosition,
+        }
+
+        impl_shared!(
+            $set_builder_name,
+            $fn_set_new,
+            $fn_set_new_shrinkable,
+            $fn_set_builder,
+            $min_or_max,
+            $arg_min_or_max,
+            $max_or_min_exists,
+            $max_or_min_exists_method,
+            $set_max_or_min_exists,
+            $set_max_or_min_exists_auto,
+            $less_or_greater,
+            A = Option<T>,
+            ASetter = T,
+            T: [Clone + cmp::PartialOrd + $max_or_min_exists],
+            $doc_fn_set_new,
+        );
+
+        impl<T> $set_builder_name<T>
+        where
+            T: Clone,
+        {
+            pub fn new(vec: Vec<T>) -> Self {
+                Self {
+                    vec,
+                    t_ord: None,
+                    t_max_or_min_exists: None,
+                    position: ArgPosition::Leftmost,
+                }
+            }
+
+            pub fn set_all_auto(self) -> Self
+            where
+                T: cmp::PartialOrd + $max_or_min_exists,
+            {
+                self.set_ord_auto().$set_max_or_min_exists_auto()
+            }
+
+            fn check(&self) {
+                self.check_ord_and_exists();
+            }
+
+            unsafe fn build_inner(
+                mut self,
+            ) -> lazy_seg_type!(
+                   T = (T, usize, usize, usize),
+                   TFolded = (T, usize),
+                   TGetter = T,
+                   TSetter = T,
+                   A = Option<T>,
+                   ASetter = T,
+               ) {
+                let (t_ord, t_max_or_min_exists) = unsafe { self.take_ord_and_exists() };
+                let position = self.position;
+                lazy_segment_tree_new_arg(
+                    self.vec,
+                    t_ord,
+                    t_max_or_min_exists,
+                    position,
+                    |x: &Option<T>, y: &Option<T>| {
+                        x.as_ref().map_or_else(|| y.clone(), |x| Some(x.clone()))
+                    },
+                    || None,
+                    // 区間全体が同じ値になるので、端の位置を取る
+                    move |a: &Option<T>, x: &T, i, first, last| match a {
+                        Some(a) => match position {
+                            ArgPosition::Leftmost => (a.clone(), first),
+                            ArgPosition::Rightmost => (a.clone(), last),
+                        },
+                        None => (x.clone(), i),
+                    },
+                )
+                .set_action_setter(|x| Some(x))
+            }
+        }
+    };
+}
+
+f!(
+    LazySegmentTreeAddArgMinBuilder,
+    LazySegmentTreeSetArgMinBuilder,
+    lazy_segment_tree_new_add_arg_min,
+    lazy_segment_tree_new_add_arg_min_shrinkable,
+    lazy_segment_tree_builder_add_arg_min,
+    lazy_segment_tree_new_set_arg_min,
+    lazy_segment_tree_new_set_arg_min_shrinkable,
+    lazy_segment_tree_builder_set_arg_min,
+    min,
+    ArgMin,
+    MaxExists,
+    max_exists,
+    set_max_exists,
+    set_max_exists_auto,
+    Less,
+    "../doc_new_add_arg_min.md",
+    "../doc_new_set_arg_min.md",
+);
+f!(
+    LazySegmentTreeAddArgMaxBuilder,
+    LazySegmentTreeSetArgMaxBuilder,
+    lazy_segment_tree_new_add_arg_max,
+    lazy_segment_tree_new_add_arg_max_shrinkable,
+    lazy_segment_tree_builder_add_arg_max,
+    lazy_segment_tree_new_set_arg_max,
+    lazy_segment_tree_new_set_arg_max_shrinkable,
+    lazy_segment_tree_builder_set_arg_max,
+    max,
+    ArgMax,
+    MinExists,
+    min_exists,
+    set_min_exists,
+    set_min_exists_auto,
+    Greater,
+    "../doc_new_add_arg_max.md",
+    "../doc_new_set_arg_max.md",
+);
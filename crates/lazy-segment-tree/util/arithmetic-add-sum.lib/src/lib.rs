@@ -0,0 +1,151 @@
+use commutative_ring::CommutativeRing;
+use int::{Int, UnsignedInt};
+use lazy_segment_tree::LazySegmentTree;
+use lazy_segment_tree_util_new_with_range::lazy_segment_tree_new_with_range;
+use lazy_segment_tree_util_type::lazy_seg_type;
+use shrink::Shrink;
+use std::fmt::Display;
+use std::rc::Rc;
+
+#[inline]
+fn convert<T, I>(x: I) -> T
+where
+    T: TryFrom<I>,
+    I: Display + Copy,
+{
+    T::try_from(x).unwrap_or_else(|_| {
+        panic!(
+            "{}: Couldn't convert index {} to T",
+            stringify!(LazySegmentTreeArithmeticAddSum),
+            x,
+        )
+    })
+}
+
+/// `from..=to` の個数と総和を `T` で返す
+#[inline]
+fn count_and_sum<T, I>(from: I, to: I) -> (T, T)
+where
+    T: CommutativeRing + TryFrom<I>,
+    I: Int,
+{
+    let count = to - from + I::one();
+    let sum = from + to;
+    // count と sum の少なくとも一方は偶数
+    let (half_count, half_sum) = if count % (I::one() + I::one()) == I::zero() {
+        (count / (I::one() + I::one()), sum)
+    } else {
+        (count, sum / (I::one() + I::one()))
+    };
+    (
+        convert(count),
+        convert::<T, I>(half_count).mul(&convert(half_sum)),
+    )
+}
+
+/// # 等差数列の加算
+///
+/// `start` 以降の各 `i` に `a + b * (i - start)` を加える作用を返す。
+#[inline]
+pub fn arithmetic_add<T, I>(start: I, a: T, b: T) -> (T, T)
+where
+    T: CommutativeRing + TryFrom<I>,
+    I: Display + Copy,
+{
+    (a.add(&b.mul(&convert(start)).neg()), b)
+}
+
+#[inline]
+fn act_op<T: CommutativeRing>((c0, c1): &(T, T), (d0, d1): &(T, T)) -> (T, T) {
+    (c0.add(d0), c1.add(d1))
+}
+
+#[inline]
+fn act_id<T: CommutativeRing>() -> (T, T) {
+    (T::zero(), T::zero())
+}
+
+#[doc = include_str!("../doc_new_arithmetic_add_sum.md")]
+pub fn lazy_segment_tree_new_arithmetic_add_sum<T>(
+    vec: Vec<T>,
+) -> lazy_seg_type!(
+    T = (T, usize, usize),
+    TFolded = T,
+    TGetter = T,
+    TSetter = T,
+    A = (T, T),
+)
+where
+    T: CommutativeRing + TryFrom<usize>,
+{
+    lazy_segment_tree_new_with_range(
+        vec,
+        |a: &T, b: &T| a.add(b),
+        || T::zero(),
+        act_op::<T>,
+        act_id::<T>,
+        |(c0, c1), t, l, r| {
+            if l >= r {
+                return t.clone();
+            }
+            let (count, sum) = count_and_sum::<T, usize>(l, r - 1);
+            t.add(&c0.mul(&count)).add(&c1.mul(&sum))
+        },
+    )
+}
+
+/// # 遅延セグメントツリー arithmetic add + sum (座標圧縮)
+///
+/// `lazy_segment_tree_new_arithmetic_add_sum` と同じだが、
+/// 添字は `shrink` によって圧縮される前の座標として扱われる。
+/// `vec` の各要素は、圧縮された区間の総和である。
+///
+/// ```
+/// use lazy_segment_tree_util_arithmetic_add_sum::{
+///     arithmetic_add, lazy_segment_tree_new_arithmetic_add_sum_shrinkable,
+/// };
+/// use shrink::shrink;
+/// // 0..=100 を 0 で埋めて、 10 と 20 で座標圧縮する
+/// let sh = shrink::<u64, i64>(vec![0, 10, 20, 100]);
+/// let mut seg = lazy_segment_tree_new_arithmetic_add_sum_shrinkable(
+///     vec![0_i64; sh.shrinked_len()],
+///     sh.clone(),
+/// );
+/// // 10..20 に 1, 2, ..., 10 を加える
+/// seg.act(sh.shrink(10..20), arithmetic_add(10_i64, 1, 1));
+/// assert_eq!(seg.fold(..), 55);
+/// assert_eq!(seg.fold(sh.shrink(10..=10)), 1);
+/// assert_eq!(seg.fold(sh.shrink(11..20)), 54);
+/// ```
+pub fn lazy_segment_tree_new_arithmetic_add_sum_shrinkable<T, USize, Index>(
+    vec: Vec<T>,
+    shrink: Rc<Shrink<USize, Index>>,
+) -> lazy_seg_type!(
+    T = (T, usize, usize),
+    TFolded = T,
+    TGetter = T,
+    TSetter = T,
+    A = (T, T),
+)
+where
+    T: CommutativeRing + TryFrom<Index>,
+    USize: UnsignedInt,
+    Index: Int<UnsignedIntSameSize = USize>,
+{
+    lazy_segment_tree_new_with_range(
+        vec,
+        |a: &T, b: &T| a.add(b),
+        || T::zero(),
+        act_op::<T>,
+        act_id::<T>,
+        move |(c0, c1), t, l, r| {
+            if l >= r {
+                return t.clone();
+            }
+            let from = *shrink.unshrink(l).unwrap_range_inclusive().start();
+            let to = *shrink.unshrink(r - 1).unwrap_range_inclusive().end();
+            let (count, sum) = count_and_sum::<T, Index>(from, to);
+            t.add(&c0.mul(&count)).add(&c1.mul(&sum))
+        },
+    )
+}
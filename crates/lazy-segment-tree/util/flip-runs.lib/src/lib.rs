@@ -0,0 +1,188 @@
+use commutative_ring::CommutativeRing;
+use int::UnsignedInt;
+use lazy_segment_tree::{lazy_segment_tree_new, LazySegmentTree};
+use lazy_segment_tree_util_type::lazy_seg_type;
+use shrink_provider::{NoShrink, ShrinkProvider};
+use std::cmp;
+
+/// 0/1 の列について、区間の長さと、 0 と 1 それぞれの連続の長さを持つ
+///
+/// `prefix`, `suffix`, `best` は `[0 の連続, 1 の連続]` の順に持つ。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BitRuns<USize: UnsignedInt> {
+    pub len: USize,
+    pub ones: USize,
+    /// 先頭から続く長さ
+    pub prefix: [USize; 2],
+    /// 末尾から続く長さ
+    pub suffix: [USize; 2],
+    /// 区間内で最長の長さ
+    pub best: [USize; 2],
+}
+
+impl<USize: UnsignedInt> BitRuns<USize> {
+    /// 空の列
+    pub fn empty() -> Self {
+        let zero = USize::zero();
+        Self {
+            len: zero,
+            ones: zero,
+            prefix: [zero; 2],
+            suffix: [zero; 2],
+            best: [zero; 2],
+        }
+    }
+
+    /// `bit` が `len` 個並んだ列
+    pub fn filled(bit: bool, len: USize) -> Self {
+        let zero = USize::zero();
+        let mut runs = [zero; 2];
+        runs[bit as usize] = len;
+        Self {
+            len,
+            ones: if bit { len } else { zero },
+            prefix: runs,
+            suffix: runs,
+            best: runs,
+        }
+    }
+
+    /// 列を連結する
+    pub fn concat(&self, other: &Self) -> Self {
+        let mut prefix = self.prefix;
+        let mut suffix = other.suffix;
+        let mut best = [USize::zero(); 2];
+        for b in 0..2 {
+            if self.prefix[b] == self.len {
+                prefix[b] = self.len + other.prefix[b];
+            }
+            if other.suffix[b] == other.len {
+                suffix[b] = self.suffix[b] + other.len;
+            }
+            best[b] = cmp::max(
+                cmp::max(self.best[b], other.best[b]),
+                self.suffix[b] + other.prefix[b],
+            );
+        }
+        Self {
+            len: self.len + other.len,
+            ones: self.ones + other.ones,
+            prefix,
+            suffix,
+            best,
+        }
+    }
+
+    /// 0 と 1 を反転する
+    pub fn flip(&self) -> Self {
+        let swap = |[a, b]: [USize; 2]| [b, a];
+        Self {
+            len: self.len,
+            ones: self.len - self.ones,
+            prefix: swap(self.prefix),
+            suffix: swap(self.suffix),
+            best: swap(self.best),
+        }
+    }
+
+    pub fn count_ones(&self) -> USize {
+        self.ones
+    }
+    pub fn count_zeros(&self) -> USize {
+        self.len - self.ones
+    }
+    pub fn longest_ones(&self) -> USize {
+        self.best[1]
+    }
+    pub fn longest_zeros(&self) -> USize {
+        self.best[0]
+    }
+}
+
+pub struct LazySegmentTreeFlipRuns<SP>
+where
+    SP: ShrinkProvider + Clone,
+{
+    vec: Vec<bool>,
+    sp: SP,
+}
+
+impl<SP> LazySegmentTreeFlipRuns<SP>
+where
+    SP: ShrinkProvider + Clone + 'static,
+{
+    pub fn new(vec: Vec<bool>, sp: SP) -> Self {
+        Self { vec, sp }
+    }
+
+    /// 作用 `true` で区間を反転する
+    pub fn build(
+        self,
+    ) -> lazy_seg_type!(
+           T = BitRuns<SP::USize>,
+           TFolded = BitRuns<SP::USize>,
+           TGetter = bool,
+           TSetter = bool,
+           A = bool,
+       ) {
+        let sp = self.sp;
+        lazy_segment_tree_new(
+            self.vec
+                .into_iter()
+                .enumerate()
+                .map(|(i, b)| BitRuns::filled(b, sp.size_of_shrinked(i)))
+                .collect(),
+            |a: &BitRuns<SP::USize>, b: &BitRuns<SP::USize>| a.concat(b),
+            BitRuns::empty,
+            |a: &bool, b: &bool| a ^ b,
+            || false,
+            |&a, t: &BitRuns<SP::USize>| if a { t.flip() } else { *t },
+        )
+        .set_value_getter(|t, _| t.ones > SP::USize::zero())
+        .set_value_setter(move |b, i| BitRuns::filled(b, sp.size_of_shrinked(i)))
+    }
+}
+
+pub fn lazy_segment_tree_builder_flip_runs_shrinkable<SP>(
+    vec: Vec<bool>,
+    sp: SP,
+) -> LazySegmentTreeFlipRuns<SP>
+where
+    SP: ShrinkProvider + Clone + 'static,
+{
+    LazySegmentTreeFlipRuns::new(vec, sp)
+}
+
+pub fn lazy_segment_tree_builder_flip_runs(vec: Vec<bool>) -> LazySegmentTreeFlipRuns<NoShrink> {
+    LazySegmentTreeFlipRuns::new(vec, NoShrink)
+}
+
+/// 各要素は圧縮された区間全体に同じ値が並んでいるものとする
+pub fn lazy_segment_tree_new_flip_runs_shrinkable<SP>(
+    vec: Vec<bool>,
+    sp: SP,
+) -> lazy_seg_type!(
+       T = BitRuns<SP::USize>,
+       TFolded = BitRuns<SP::USize>,
+       TGetter = bool,
+       TSetter = bool,
+       A = bool,
+   )
+where
+    SP: ShrinkProvider + Clone + 'static,
+{
+    lazy_segment_tree_builder_flip_runs_shrinkable(vec, sp).build()
+}
+
+#[doc = include_str!("../doc_new_flip_runs.md")]
+pub fn lazy_segment_tree_new_flip_runs(
+    vec: Vec<bool>,
+) -> lazy_seg_type!(
+       T = BitRuns<usize>,
+       TFolded = BitRuns<usize>,
+       TGetter = bool,
+       TSetter = bool,
+       A = bool,
+   ) {
+    lazy_segment_tree_builder_flip_runs(vec).build()
+}
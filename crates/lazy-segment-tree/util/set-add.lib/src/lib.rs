@@ -0,0 +1,587 @@
+use commutative_ring::CommutativeRing;
+use commutative_ring_ord::CommutativeRingOrd;
+use lazy_segment_tree::LazySegmentTree;
+use lazy_segment_tree_util_new_with_len::lazy_segment_tree_new_with_len_shrinkable;
+use lazy_segment_tree_util_type::lazy_seg_type;
+use max_exists::MaxExists;
+use min_exists::MinExists;
+use min_max_count::{MaxCount, MinCount};
+use shrink_provider::{NoShrink, ShrinkProvider};
+use std::cmp;
+use std::ops;
+use std::rc::Rc;
+
+/// # セットまたは加算の作用
+///
+/// 内部では、省略可能なセットの後に加算を行う作用 `(Option<T>, T)` として持つ。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SetOrAdd<T> {
+    Set(T),
+    Add(T),
+}
+
+/// `(set, add)` の後に `(set, add)` を作用させたもの
+#[inline]
+fn compose<T: Clone>(
+    t_add: &dyn Fn(&T, &T) -> T,
+    (new_set, new_add): &(Option<T>, T),
+    (old_set, old_add): &(Option<T>, T),
+) -> (Option<T>, T) {
+    if new_set.is_some() {
+        (new_set.clone(), new_add.clone())
+    } else {
+        (old_set.clone(), t_add(old_add, new_add))
+    }
+}
+
+pub struct LazySegmentTreeSetAddSum<T, SP>
+where
+    T: Clone,
+    SP: ShrinkProvider + Clone,
+{
+    vec: Vec<T>,
+    t_add: Option<Box<dyn Fn(&T, &T) -> T>>,
+    t_zero: Option<Box<dyn Fn() -> T>>,
+    t_mul_usize: Option<Box<dyn Fn(&T, SP::USize) -> T>>,
+    sp: SP,
+}
+
+impl<T, SP> LazySegmentTreeSetAddSum<T, SP>
+where
+    T: Clone,
+    SP: ShrinkProvider + Clone,
+{
+    pub fn new(vec: Vec<T>, sp: SP) -> Self {
+        Self {
+            vec,
+            t_add: None,
+            t_zero: None,
+            t_mul_usize: None,
+            sp,
+        }
+    }
+
+    pub fn set_add(mut self, t_add: impl Fn(&T, &T) -> T + 'static) -> Self {
+        self.t_add = Some(Box::new(t_add));
+        self
+    }
+    pub fn set_add_zero_by_commutative_ring_add(self) -> Self
+    where
+        T: CommutativeRing,
+    {
+        self.set_add(|a, b| a.add(b)).set_zero(|| T::zero())
+    }
+    pub fn set_add_by_add(self) -> Self
+    where
+        T: ops::Add<Output = T>,
+    {
+        self.set_add(|a, b| a.clone() + b.clone())
+    }
+
+    pub fn set_zero(mut self, t_zero: impl Fn() -> T + 'static) -> Self {
+        self.t_zero = Some(Box::new(t_zero));
+        self
+    }
+    pub fn set_zero_by_default(self) -> Self
+    where
+        T: Default,
+    {
+        self.set_zero(|| T::default())
+    }
+
+    pub fn set_mul_usize(mut self, t_mul_usize: impl Fn(&T, SP::USize) -> T + 'static) -> Self {
+        self.t_mul_usize = Some(Box::new(t_mul_usize));
+        self
+    }
+    pub fn set_mul_usize_auto(self) -> Self
+    where
+        T: TryFrom<SP::USize> + ops::Mul<Output = T>,
+    {
+        self.set_mul_usize_by_mul()
+    }
+    pub fn set_mul_usize_by_mul(self) -> Self
+    where
+        T: TryFrom<SP::USize> + ops::Mul<Output = T>,
+    {
+        self.set_mul_usize(|x, len| {
+            let len = len.try_into().unwrap_or_else(|_| {
+                panic!(
+                    "{}: Couldn't convert USize {} to T",
+                    stringify!(LazySegmentTreeSetAddSum),
+                    len,
+                )
+            });
+            x.clone() * len
+        })
+    }
+
+    pub fn build(
+        self,
+    ) -> lazy_seg_type!(
+           T = (T, SP::USize),
+           TFolded = T,
+           TGetter = T,
+           TSetter = T,
+           A = (Option<T>, T),
+           ASetter = SetOrAdd<T>,
+       ) {
+        self.t_add
+            .as_ref()
+            .or_else(|| panic!("{}: add is not set", stringify!(LazySegmentTreeSetAddSum)));
+        self.t_zero
+            .as_ref()
+            .or_else(|| panic!("{}: zero is not set", stringify!(LazySegmentTreeSetAddSum)));
+        self.t_mul_usize.as_ref().or_else(|| {
+            panic!(
+                "{}: mul_usize is not set",
+                stringify!(LazySegmentTreeSetAddSum),
+            )
+        });
+        unsafe { self.build_unchecked() }
+    }
+
+    /// ## Safety
+    /// - すべてのメソッドが設定されていること
+    pub unsafe fn build_unchecked(
+        self,
+    ) -> lazy_seg_type!(
+           T = (T, SP::USize),
+           TFolded = T,
+           TGetter = T,
+           TSetter = T,
+           A = (Option<T>, T),
+           ASetter = SetOrAdd<T>,
+       ) {
+        let t_add = Rc::new(unsafe { self.t_add.unwrap_unchecked() });
+        let t_zero = Rc::new(unsafe { self.t_zero.unwrap_unchecked() });
+        let t_mul_usize = unsafe { self.t_mul_usize.unwrap_unchecked() };
+        lazy_segment_tree_new_with_len_shrinkable(
+            self.vec,
+            {
+                let t_add = t_add.clone();
+                move |a, b| t_add(a, b)
+            },
+            {
+                let t_zero = t_zero.clone();
+                move || t_zero()
+            },
+            {
+                let t_add = t_add.clone();
+                move |x: &(Option<T>, T), y: &(Option<T>, T)| compose(&*t_add, x, y)
+            },
+            {
+                let t_zero = t_zero.clone();
+                move || (None, t_zero())
+            },
+            move |(set, add): &(Option<T>, T), a, len| {
+                let base = set
+                    .as_ref()
+                    .map_or_else(|| a.clone(), |set| t_mul_usize(set, len));
+                t_add(&base, &t_mul_usize(add, len))
+            },
+            self.sp,
+        )
+        .set_action_setter(move |x| match x {
+            SetOrAdd::Set(x) => (Some(x), t_zero()),
+            SetOrAdd::Add(x) => (None, x),
+        })
+    }
+}
+
+pub fn lazy_segment_tree_builder_set_add_sum_shrinkable<T, SP>(
+    vec: Vec<T>,
+    sp: SP,
+) -> LazySegmentTreeSetAddSum<T, SP>
+where
+    T: Clone,
+    SP: ShrinkProvider + Clone,
+{
+    LazySegmentTreeSetAddSum::new(vec, sp)
+}
+
+pub fn lazy_segment_tree_builder_set_add_sum<T>(
+    vec: Vec<T>,
+) -> LazySegmentTreeSetAddSum<T, NoShrink>
+where
+    T: Clone,
+{
+    LazySegmentTreeSetAddSum::new(vec, NoShrink)
+}
+
+pub fn lazy_segment_tree_new_set_add_sum_shrinkable<T, SP>(
+    vec: Vec<T>,
+    sp: SP,
+) -> lazy_seg_type!(
+       T = (T, SP::USize),
+       TFolded = T,
+       TGetter = T,
+       TSetter = T,
+       A = (Option<T>, T),
+       ASetter = SetOrAdd<T>,
+   )
+where
+    T: Clone + CommutativeRing + TryFrom<SP::USize> + ops::Mul<Output = T>,
+    SP: ShrinkProvider + Clone,
+{
+    let b = lazy_segment_tree_builder_set_add_sum_shrinkable(vec, sp)
+        .set_add_zero_by_commutative_ring_add()
+        .set_mul_usize_by_mul();
+    unsafe { b.build_unchecked() }
+}
+
+#[doc = include_str!("../doc_new_set_add_sum.md")]
+pub fn lazy_segment_tree_new_set_add_sum<T>(
+    vec: Vec<T>,
+) -> lazy_seg_type!(
+       T = (T, usize),
+       TFolded = T,
+       TGetter = T,
+       TSetter = T,
+       A = (Option<T>, T),
+       ASetter = SetOrAdd<T>,
+   )
+where
+    T: Clone + CommutativeRing + TryFrom<usize> + ops::Mul<Output = T>,
+{
+    lazy_segment_tree_new_set_add_sum_shrinkable(vec, NoShrink)
+}
+
+macro_rules! f {
+    (
+        $builder_name:ident,
+        $fn_new_shrinkable:ident,
+        $fn_new:ident,
+        $fn_builder_shrinkable:ident,
+        $fn_builder:ident,
+        $min_or_max:ident,
+        $min_or_max_count:ident,
+        $max_or_min_exists:ident,
+        $max_or_min_exists_method:ident,
+        $set_max_or_min_exists:ident,
+        $set_max_or_min_exists_auto:ident,
+        $greater_or_less:ident,
+        $less_or_greater:ident,
+        $doc_fn_new:expr $(,)?
+    ) => {
+        pub struct $builder_name<T, SP>
+        where
+            T: Clone,
+            SP: ShrinkProvider + Clone,
+        {
+            vec: Vec<T>,
+            t_add: Option<Box<dyn Fn(&T, &T) -> T>>,
+            t_zero: Option<Box<dyn Fn() -> T>>,
+            t_ord: Option<Box<dyn Fn(&T, &T) -> cmp::Ordering>>,
+            t_max_or_min_exists: Option<Box<dyn Fn() -> T>>,
+            sp: SP,
+        }
+
+        impl<T, SP> $builder_name<T, SP>
+        where
+            T: Clone,
+            SP: ShrinkProvider + Clone,
+        {
+            pub fn new(vec: Vec<T>, sp: SP) -> Self {
+                Self {
+                    vec,
+                    t_add: None,
+                    t_zero: None,
+                    t_ord: None,
+                    t_max_or_min_exists: None,
+                    sp,
+                }
+            }
+
+            pub fn set_all_auto(self) -> Self
+            where
+                T: CommutativeRingOrd + cmp::PartialOrd + $max_or_min_exists,
+            {
+                self.set_add_auto()
+                    .set_zero_auto()
+                    .set_ord_auto()
+                    .$set_max_or_min_exists_auto()
+            }
+
+            pub fn set_add(mut self, t_add: impl Fn(&T, &T) -> T + 'static) -> Self {
+                self.t_add = Some(Box::new(t_add));
+                self
+            }
+            pub fn set_add_auto(self) -> Self
+            where
+                T: CommutativeRingOrd,
+            {
+                self.set_add_by_commutative_ring_ord()
+            }
+            pub fn set_add_by_commutative_ring_ord(self) -> Self
+            where
+                T: CommutativeRingOrd,
+            {
+                self.set_add(|a, b| a.add(b))
+            }
+            pub fn set_add_by_add(self) -> Self
+            where
+                T: ops::Add<Output = T>,
+            {
+                self.set_add(|a, b| a.clone() + b.clone())
+            }
+
+            pub fn set_zero(mut self, t_zero: impl Fn() -> T + 'static) -> Self {
+                self.t_zero = Some(Box::new(t_zero));
+                self
+            }
+            pub fn set_zero_auto(self) -> Self
+            where
+                T: CommutativeRingOrd,
+            {
+                self.set_zero_by_commutative_ring_ord()
+            }
+            pub fn set_zero_by_commutative_ring_ord(self) -> Self
+            where
+                T: CommutativeRingOrd,
+            {
+                self.set_zero(|| T::zero())
+            }
+            pub fn set_zero_by_default(self) -> Self
+            where
+                T: Default,
+            {
+                self.set_zero(|| T::default())
+            }
+
+            pub fn set_ord(mut self, t_ord: impl Fn(&T, &T) -> cmp::Ordering + 'static) -> Self {
+                self.t_ord = Some(Box::new(t_ord));
+                self
+            }
+            pub fn set_ord_auto(self) -> Self
+            where
+                T: std::cmp::PartialOrd,
+            {
+                self.set_ord_by_partial_ord()
+            }
+            pub fn set_ord_by_ord(self) -> Self
+            where
+                T: std::cmp::Ord,
+            {
+                self.set_ord(|a, b| a.cmp(b))
+            }
+            pub fn set_ord_by_partial_ord(self) -> Self
+            where
+                T: std::cmp::PartialOrd,
+            {
+                self.set_ord(|a, b| {
+                    a.partial_cmp(b).unwrap_or_else(|| {
+                        panic!("{}: partial_cmp should be total", stringify!($builder_name))
+                    })
+                })
+            }
+
+            pub fn $set_max_or_min_exists(
+                mut self,
+                t_max_or_min_exists: impl Fn() -> T + 'static,
+            ) -> Self {
+                self.t_max_or_min_exists = Some(Box::new(t_max_or_min_exists));
+                self
+            }
+            pub fn $set_max_or_min_exists_auto(self) -> Self
+            where
+                T: $max_or_min_exists,
+            {
+                self.$set_max_or_min_exists(|| T::$max_or_min_exists_method())
+            }
+
+            pub fn build(
+                self,
+            ) -> lazy_seg_type!(
+                   T = ($min_or_max_count<T, SP::USize>, SP::USize),
+                   TFolded = $min_or_max_count<T, SP::USize>,
+                   TGetter = T,
+                   TSetter = T,
+                   A = (Option<T>, T),
+                   ASetter = SetOrAdd<T>,
+               ) {
+                self.t_add
+                    .as_ref()
+                    .or_else(|| panic!("{}: add is not set", stringify!($builder_name)));
+                self.t_zero
+                    .as_ref()
+                    .or_else(|| panic!("{}: zero is not set", stringify!($builder_name)));
+                self.t_max_or_min_exists.as_ref().or_else(|| {
+                    panic!(
+                        "{}: max_or_min_exists is not set",
+                        stringify!($builder_name)
+                    )
+                });
+                self.t_ord
+                    .as_ref()
+                    .or_else(|| panic!("{}: ord is not set", stringify!($builder_name)));
+                unsafe { self.build_unchecked() }
+            }
+            /// ## Safety
+            /// - すべてのメソッドが設定されていること
+            pub unsafe fn build_unchecked(
+                self,
+            ) -> lazy_seg_type!(
+                   T = ($min_or_max_count<T, SP::USize>, SP::USize),
+                   TFolded = $min_or_max_count<T, SP::USize>,
+                   TGetter = T,
+                   TSetter = T,
+                   A = (Option<T>, T),
+                   ASetter = SetOrAdd<T>,
+               ) {
+                let t_add = Rc::new(unsafe { self.t_add.unwrap_unchecked() });
+                let t_zero = Rc::new(unsafe { self.t_zero.unwrap_unchecked() });
+                let t_max_or_min_exists = unsafe { self.t_max_or_min_exists.unwrap_unchecked() };
+                let t_ord = unsafe { self.t_ord.unwrap_unchecked() };
+                lazy_segment_tree_new_with_len_shrinkable(
+                    self.vec
+                        .into_iter()
+                        .enumerate()
+                        .map({
+                            let sp = self.sp.clone();
+                            move |(i, $min_or_max)| $min_or_max_count {
+                                $min_or_max,
+                                count: sp.size_of_shrinked(i),
+                            }
+                        })
+                        .collect(),
+                    move |a: &$min_or_max_count<_, _>, b: &$min_or_max_count<_, _>| match (t_ord)(
+                        &a.$min_or_max,
+                        &b.$min_or_max,
+                    ) {
+                        cmp::Ordering::$greater_or_less => b.clone(),
+                        cmp::Ordering::$less_or_greater => a.clone(),
+                        cmp::Ordering::Equal => $min_or_max_count {
+                            $min_or_max: a.$min_or_max.clone(),
+                            count: a.count + b.count,
+                        },
+                    },
+                    move || $min_or_max_count {
+                        $min_or_max: t_max_or_min_exists(),
+                        count: SP::USize::zero(),
+                    },
+                    {
+                        let t_add = t_add.clone();
+                        move |x: &(Option<T>, T), y: &(Option<T>, T)| compose(&*t_add, x, y)
+                    },
+                    {
+                        let t_zero = t_zero.clone();
+                        move || (None, t_zero())
+                    },
+                    move |(set, add): &(Option<T>, T), a, len| match set {
+                        Some(set) => $min_or_max_count {
+                            $min_or_max: t_add(set, add),
+                            count: len,
+                        },
+                        None => $min_or_max_count {
+                            $min_or_max: t_add(&a.$min_or_max, add),
+                            count: a.count,
+                        },
+                    },
+                    self.sp.clone(),
+                )
+                .set_value_getter(|(x, _), _| x.$min_or_max.clone())
+                .set_value_setter({
+                    let sp = self.sp;
+                    move |x, i| {
+                        (
+                            $min_or_max_count {
+                                $min_or_max: x,
+                                count: sp.size_of_shrinked(i),
+                            },
+                            sp.size_of_shrinked(i),
+                        )
+                    }
+                })
+                .set_action_setter(move |x| match x {
+                    SetOrAdd::Set(x) => (Some(x), t_zero()),
+                    SetOrAdd::Add(x) => (None, x),
+                })
+            }
+        }
+
+        pub fn $fn_builder_shrinkable<T, SP>(vec: Vec<T>, sp: SP) -> $builder_name<T, SP>
+        where
+            T: Clone,
+            SP: ShrinkProvider + Clone,
+        {
+            $builder_name::new(vec, sp)
+        }
+
+        pub fn $fn_builder<T>(vec: Vec<T>) -> $builder_name<T, NoShrink>
+        where
+            T: Clone,
+        {
+            $builder_name::new(vec, NoShrink)
+        }
+
+        pub fn $fn_new_shrinkable<T, SP>(
+            vec: Vec<T>,
+            sp: SP,
+        ) -> lazy_seg_type!(
+              T = ($min_or_max_count<T, SP::USize>, SP::USize),
+              TFolded = $min_or_max_count<T, SP::USize>,
+              TGetter = T,
+              TSetter = T,
+              A = (Option<T>, T),
+              ASetter = SetOrAdd<T>,
+           )
+        where
+            T: Clone + CommutativeRingOrd + std::cmp::PartialOrd + $max_or_min_exists,
+            SP: ShrinkProvider + Clone,
+        {
+            let b = $fn_builder_shrinkable(vec, sp).set_all_auto();
+            unsafe { b.build_unchecked() }
+        }
+
+        #[doc = include_str!($doc_fn_new)]
+        pub fn $fn_new<T>(
+            vec: Vec<T>,
+        ) -> lazy_seg_type!(
+              T = ($min_or_max_count<T, usize>, usize),
+              TFolded = $min_or_max_count<T, usize>,
+              TGetter = T,
+              TSetter = T,
+              A = (Option<T>, T),
+              ASetter = SetOrAdd<T>,
+           )
+        where
+            T: Clone + CommutativeRingOrd + std::cmp::PartialOrd + $max_or_min_exists,
+        {
+            let b = $fn_builder(vec).set_all_auto();
+            unsafe { b.build_unchecked() }
+        }
+    };
+}
+
+f!(
+    LazySegmentTreeSetAddMinCountBuilder,
+    lazy_segment_tree_new_set_add_min_count_shrinkable,
+    lazy_segment_tree_new_set_add_min_count,
+    lazy_segment_tree_builder_set_add_min_count_shrinkable,
+    lazy_segment_tree_builder_set_add_min_count,
+    min,
+    MinCount,
+    MaxExists,
+    max_exists,
+    set_max_exists,
+    set_max_exists_auto,
+    Greater,
+    Less,
+    "../doc_new_set_add_min_count.md",
+);
+f!(
+    LazySegmentTreeSetAddMaxCountBuilder,
+    lazy_segment_tree_new_set_add_max_count_shrinkable,
+    lazy_segment_tree_new_set_add_max_count,
+    lazy_segment_tree_builder_set_add_max_count_shrinkable,
+    lazy_segment_tree_builder_set_add_max_count,
+    max,
+    MaxCount,
+    MinExists,
+    min_exists,
+    set_min_exists,
+    set_min_exists_auto,
+    Less,
+    Greater,
+    "../doc_new_set_add_max_count.md",
+);
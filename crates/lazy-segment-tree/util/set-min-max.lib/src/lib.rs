@@ -1,4 +1,6 @@
-use lazy_segment_tree::{lazy_segment_tree_new, LazySegmentTree};
+use lazy_segment_tree::{
+    lazy_segment_tree_new, lazy_segment_tree_new_static, LazySegmentTree, LazySegmentTreeStatic,
+};
 use lazy_segment_tree_util_type::lazy_seg_type;
 use max_exists::MaxExists;
 use min_exists::MinExists;
@@ -130,18 +132,37 @@ macro_rules! f {
         }
 
         #[doc = include_str!($doc_fn_new)]
-        pub fn $fn_new<T>(
-            vec: Vec<T>,
-        ) -> lazy_seg_type!(
-               T = T,
-               A = Option<T>,
-               ASetter = T,
-           )
+        pub fn $fn_new<T>(vec: Vec<T>) -> LazySegmentTreeStatic<T, T, T, T, Option<T>, T>
         where
             T: Clone + std::cmp::PartialOrd + $max_or_min_exists,
         {
-            let b = $fn_builder(vec).set_all_auto();
-            unsafe { b.build_unchecked() }
+            fn op<T: Clone + cmp::PartialOrd>(a: &T, b: &T) -> T {
+                match a.partial_cmp(b) {
+                    Some(cmp::Ordering::$greater_or_less | cmp::Ordering::Equal) => b.clone(),
+                    Some(cmp::Ordering::$less_or_greater) => a.clone(),
+                    None => {
+                        panic!("order should be total")
+                    }
+                }
+            }
+            fn act_op<T: Clone>(x: &Option<T>, y: &Option<T>) -> Option<T> {
+                x.as_ref().map_or_else(|| y.clone(), |x| Some(x.clone()))
+            }
+            fn act_id<T>() -> Option<T> {
+                None
+            }
+            fn act_app<T: Clone>(x: &Option<T>, a: &T) -> T {
+                x.as_ref().map_or_else(|| a.clone(), |x| x.clone())
+            }
+            lazy_segment_tree_new_static(
+                vec,
+                op::<T>,
+                T::$max_or_min_exists_method,
+                act_op::<T>,
+                act_id::<T>,
+                act_app::<T>,
+            )
+            .set_action_setter(Some as fn(_) -> _)
         }
     };
 }
@@ -2,7 +2,7 @@ use commutative_ring::CommutativeRing;
 use commutative_ring_as_additive_group::AdditiveGroup;
 use commutative_ring_as_multicative_commutative_monoid::MulticativeCommutativeMonoid;
 use group_as_monoid::GroupAsMonoid;
-use lazy_segment_tree::LazySegmentTree;
+use lazy_segment_tree::{lazy_segment_tree_new_static, LazySegmentTree, LazySegmentTreeStatic};
 use lazy_segment_tree_util_new_with_len::lazy_segment_tree_new_with_len_shrinkable;
 use lazy_segment_tree_util_type::lazy_seg_type;
 use monoid::Monoid;
@@ -94,7 +94,7 @@ where
         self.set_mul_usize(|x, len| {
             let len = len.try_into().unwrap_or_else(|_| {
                 panic!(
-                    "{}: Couldn't convert USize {} to usize",
+                    "{}: Couldn't convert USize {} to T",
                     stringify!(LazySegmentTreeSetSum),
                     len,
                 )
@@ -256,11 +256,62 @@ where
 #[doc = include_str!("../doc_new_set_sum.md")]
 pub fn lazy_segment_tree_new_set_sum<T>(
     vec: Vec<T>,
-) -> lazy_seg_type!(T = (T, usize), TFolded = T, TGetter = T, TSetter = T, A = Option<T>, ASetter = T)
+) -> LazySegmentTreeStatic<(T, usize), T, T, T, Option<T>, T>
 where
     T: Clone + CommutativeRing + TryFrom<usize> + ops::Mul<Output = T>,
 {
-    lazy_segment_tree_new_set_sum_shrinkable(vec, NoShrink)
+    fn op<T: CommutativeRing>((a, a_len): &(T, usize), (b, b_len): &(T, usize)) -> (T, usize) {
+        (a.add(b), a_len + b_len)
+    }
+    fn id<T: CommutativeRing>() -> (T, usize) {
+        (T::zero(), 0)
+    }
+    fn act_op<T: Clone>(x: &Option<T>, y: &Option<T>) -> Option<T> {
+        x.as_ref().map_or_else(|| y.clone(), |x| Some(x.clone()))
+    }
+    fn act_id<T>() -> Option<T> {
+        None
+    }
+    fn act_app<T>(x: &Option<T>, (a, len): &(T, usize)) -> (T, usize)
+    where
+        T: Clone + TryFrom<usize> + ops::Mul<Output = T>,
+    {
+        let t = x.as_ref().map_or_else(
+            || a.clone(),
+            |x| {
+                let len_t = T::try_from(*len).unwrap_or_else(|_| {
+                    panic!(
+                        "{}: Couldn't convert USize {} to T",
+                        stringify!(LazySegmentTreeSetSum),
+                        len,
+                    )
+                });
+                x.clone() * len_t
+            },
+        );
+        (t, *len)
+    }
+    fn into_folded<T>((t, _): (T, usize)) -> T {
+        t
+    }
+    fn into_getter<T>((t, _): (T, usize), _: usize) -> T {
+        t
+    }
+    fn from_setter<T>(t: T, _: usize) -> (T, usize) {
+        (t, 1)
+    }
+    lazy_segment_tree_new_static(
+        vec.into_iter().map(|x| (x, 1)).collect(),
+        op::<T>,
+        id::<T>,
+        act_op::<T>,
+        act_id::<T>,
+        act_app::<T>,
+    )
+    .set_value_folded(into_folded::<T> as fn(_) -> _)
+    .set_value_getter(into_getter::<T> as fn(_, _) -> _)
+    .set_value_setter(from_setter::<T> as fn(_, _) -> _)
+    .set_action_setter(Some as fn(_) -> _)
 }
 
 pub fn lazy_segment_tree_new_set_sum_com_ring_add_shrinkable<T, SP>(
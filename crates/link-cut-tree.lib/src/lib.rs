@@ -0,0 +1,493 @@
+//! # Link-Cut Tree
+//!
+//! 森に対して、辺の追加・削除、根の変更、パスの畳み込み、パスへの作用を
+//! 償却 $O(\log N)$ で行う。
+//!
+//! 根の変更でパスの向きが反転するので、各ノードの値は [`ReversibleFold`] で持つ。
+//! よって非可換なモノイドでもパスの向きどおりに畳み込まれる。
+//! 作用は反転と可換である必要がある。
+
+use monoid::{Monoid, QuickMonoid};
+use monoid_action::{MonoidAction, QuickMonoidAction, QuickMonoidActionTrivial};
+use reversible_fold::ReversibleFold;
+
+const NIL: usize = usize::MAX;
+
+struct Node<T, A> {
+    left: usize,
+    right: usize,
+    /// splay 木の親、または splay 木の根であればパスの親
+    parent: usize,
+    data: ReversibleFold<T, A>,
+}
+
+/// # Link-Cut Tree
+pub struct LinkCutTree<T, A, Op, Id, ActOp, ActId, ActApp>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+    ActOp: Fn(&A, &A) -> A,
+    ActId: Fn() -> A,
+    ActApp: Fn(&A, &T) -> T,
+{
+    nodes: Vec<Node<T, A>>,
+    monoid_action: QuickMonoidAction<T, A, Op, Id, ActOp, ActId, ActApp>,
+}
+
+/// # 名前を付けられる Link-Cut Tree の型
+pub type LinkCutTreeStatic<T, A = ()> =
+    LinkCutTree<T, A, fn(&T, &T) -> T, fn() -> T, fn(&A, &A) -> A, fn() -> A, fn(&A, &T) -> T>;
+
+impl<T, A, Op, Id, ActOp, ActId, ActApp> LinkCutTree<T, A, Op, Id, ActOp, ActId, ActApp>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+    ActOp: Fn(&A, &A) -> A,
+    ActId: Fn() -> A,
+    ActApp: Fn(&A, &T) -> T,
+{
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// # 根の変更
+    ///
+    /// `v` を属する木の根にする。
+    ///
+    /// ## 計算量
+    ///
+    /// 償却 $O(\log N)$
+    pub fn evert(&mut self, v: usize) {
+        self.access(v);
+        self.reverse(v);
+        self.push(v);
+    }
+
+    /// # 辺の追加
+    ///
+    /// ## Panics
+    ///
+    /// - `u` と `v` がすでに連結である
+    ///
+    /// ## 計算量
+    ///
+    /// 償却 $O(\log N)$
+    pub fn link(&mut self, u: usize, v: usize) {
+        assert!(!self.connected(u, v), "link-cut-tree: already connected");
+        self.evert(u);
+        self.nodes[u].parent = v;
+    }
+
+    /// # 辺の削除
+    ///
+    /// ## Panics
+    ///
+    /// - 辺 `u`-`v` が存在しない
+    ///
+    /// ## 計算量
+    ///
+    /// 償却 $O(\log N)$
+    pub fn cut(&mut self, u: usize, v: usize) {
+        self.evert(u);
+        self.access(v);
+        assert!(
+            self.nodes[v].left == u && self.nodes[u].left == NIL && self.nodes[u].right == NIL,
+            "link-cut-tree: no edge {}-{}",
+            u,
+            v
+        );
+        self.nodes[v].left = NIL;
+        self.nodes[u].parent = NIL;
+        self.update(v);
+    }
+
+    /// # 属する木の根
+    ///
+    /// ## 計算量
+    ///
+    /// 償却 $O(\log N)$
+    pub fn root(&mut self, v: usize) -> usize {
+        self.access(v);
+        let mut x = v;
+        loop {
+            self.push(x);
+            if self.nodes[x].left == NIL {
+                break;
+            }
+            x = self.nodes[x].left;
+        }
+        self.splay(x);
+        x
+    }
+
+    /// # 連結判定
+    ///
+    /// ## 計算量
+    ///
+    /// 償却 $O(\log N)$
+    pub fn connected(&mut self, u: usize, v: usize) -> bool {
+        u == v || self.root(u) == self.root(v)
+    }
+
+    /// # 最小共通祖先
+    ///
+    /// 現在の根に関する最小共通祖先。連結でなければ `None` 。
+    ///
+    /// ## 計算量
+    ///
+    /// 償却 $O(\log N)$
+    pub fn lca(&mut self, u: usize, v: usize) -> Option<usize> {
+        if !self.connected(u, v) {
+            return None;
+        }
+        self.access(u);
+        Some(self.access(v))
+    }
+
+    /// # 頂点の値の取得
+    ///
+    /// ## 計算量
+    ///
+    /// 償却 $O(\log N)$
+    pub fn get(&mut self, v: usize) -> T {
+        self.access(v);
+        self.nodes[v].data.value.clone()
+    }
+
+    /// # 頂点の値の変更
+    ///
+    /// ## 計算量
+    ///
+    /// 償却 $O(\log N)$
+    pub fn set(&mut self, v: usize, x: T) {
+        self.access(v);
+        self.nodes[v].data.value = x;
+        self.update(v);
+    }
+
+    /// # パスの畳み込み
+    ///
+    /// `u` から `v` へたどる順に、両端を含む頂点の値を畳み込む。
+    /// 根は `u` に変更される。
+    ///
+    /// ## Panics
+    ///
+    /// - `u` と `v` が連結でない
+    ///
+    /// ## 計算量
+    ///
+    /// 償却 $O(\log N)$
+    pub fn fold(&mut self, u: usize, v: usize) -> T {
+        self.expose_path(u, v);
+        self.nodes[v].data.fold.clone()
+    }
+
+    /// # パスへの作用
+    ///
+    /// `u` から `v` へのパス上の、両端を含む頂点に作用させる。
+    /// 根は `u` に変更される。
+    ///
+    /// ## Panics
+    ///
+    /// - `u` と `v` が連結でない
+    ///
+    /// ## 計算量
+    ///
+    /// 償却 $O(\log N)$
+    pub fn act(&mut self, u: usize, v: usize, a: A) {
+        self.expose_path(u, v);
+        self.apply(v, &a);
+    }
+
+    /// `u` を根にして、 `u` から `v` へのパスを `v` を根とする splay 木にする
+    fn expose_path(&mut self, u: usize, v: usize) {
+        assert!(self.connected(u, v), "link-cut-tree: not connected");
+        self.evert(u);
+        self.access(v);
+    }
+
+    /// 根から `v` へのパスを `v` を根とする splay 木にする。
+    /// 最後にパスへ合流した頂点を返す。
+    fn access(&mut self, v: usize) -> usize {
+        let mut last = NIL;
+        let mut x = v;
+        while x != NIL {
+            self.splay(x);
+            self.nodes[x].right = last;
+            self.update(x);
+            last = x;
+            x = self.nodes[x].parent;
+        }
+        self.splay(v);
+        last
+    }
+
+    fn is_splay_root(&self, x: usize) -> bool {
+        let p = self.nodes[x].parent;
+        p == NIL || (self.nodes[p].left != x && self.nodes[p].right != x)
+    }
+
+    fn splay(&mut self, x: usize) {
+        let mut path = vec![x];
+        let mut y = x;
+        while !self.is_splay_root(y) {
+            y = self.nodes[y].parent;
+            path.push(y);
+        }
+        for &y in path.iter().rev() {
+            self.push(y);
+        }
+        while !self.is_splay_root(x) {
+            let p = self.nodes[x].parent;
+            if !self.is_splay_root(p) {
+                let g = self.nodes[p].parent;
+                if (self.nodes[g].left == p) == (self.nodes[p].left == x) {
+                    self.rotate(p);
+                } else {
+                    self.rotate(x);
+                }
+            }
+            self.rotate(x);
+        }
+    }
+
+    fn rotate(&mut self, x: usize) {
+        let p = self.nodes[x].parent;
+        let g = self.nodes[p].parent;
+        if self.nodes[p].left == x {
+            let c = self.nodes[x].right;
+            self.nodes[p].left = c;
+            if c != NIL {
+                self.nodes[c].parent = p;
+            }
+            self.nodes[x].right = p;
+        } else {
+            let c = self.nodes[x].left;
+            self.nodes[p].right = c;
+            if c != NIL {
+                self.nodes[c].parent = p;
+            }
+            self.nodes[x].left = p;
+        }
+        self.nodes[p].parent = x;
+        self.nodes[x].parent = g;
+        if g != NIL {
+            if self.nodes[g].left == p {
+                self.nodes[g].left = x;
+            } else if self.nodes[g].right == p {
+                self.nodes[g].right = x;
+            }
+        }
+        self.update(p);
+        self.update(x);
+    }
+
+    fn data(&self, x: usize) -> Option<&ReversibleFold<T, A>> {
+        (x != NIL).then(|| &self.nodes[x].data)
+    }
+
+    fn update(&mut self, x: usize) {
+        let (l, r) = (self.nodes[x].left, self.nodes[x].right);
+        let folds = self.nodes[x]
+            .data
+            .folds(&self.monoid_action, self.data(l), self.data(r));
+        let data = &mut self.nodes[x].data;
+        (data.fold, data.rev_fold) = folds;
+    }
+
+    fn reverse(&mut self, x: usize) {
+        self.nodes[x].data.reverse();
+    }
+
+    fn apply(&mut self, x: usize, a: &A) {
+        self.nodes[x].data.apply(&self.monoid_action, a);
+    }
+
+    fn push(&mut self, x: usize) {
+        let (l, r) = (self.nodes[x].left, self.nodes[x].right);
+        let [x_data, l_data, r_data] = data_mut(&mut self.nodes, [x, l, r]);
+        let x_data = x_data.unwrap();
+        if x_data.push_to(&self.monoid_action, l_data, r_data) {
+            let node = &mut self.nodes[x];
+            std::mem::swap(&mut node.left, &mut node.right);
+        }
+    }
+}
+
+/// 相異なる添字のノードの値を同時に借用する (`NIL` は `None` になる)
+fn data_mut<T, A>(
+    nodes: &mut [Node<T, A>],
+    idx: [usize; 3],
+) -> [Option<&mut ReversibleFold<T, A>>; 3] {
+    let mut order = [0, 1, 2];
+    order.sort_unstable_by_key(|&k| idx[k]);
+    let mut res = [None, None, None];
+    let mut rest = nodes;
+    let mut offset = 0;
+    for k in order {
+        if idx[k] == NIL {
+            continue;
+        }
+        let (_, tail) = std::mem::take(&mut rest).split_at_mut(idx[k] - offset);
+        let (node, tail) = tail.split_first_mut().unwrap();
+        res[k] = Some(&mut node.data);
+        rest = tail;
+        offset = idx[k] + 1;
+    }
+    res
+}
+
+/// # Link-Cut Tree の構築 (`QuickMonoidAction` による指定)
+///
+/// 頂点 `i` の値を `values[i]` とし、辺のない森として構築する。
+pub fn link_cut_tree_new_quick_monoid_action<T, A, Op, Id, ActOp, ActId, ActApp>(
+    values: Vec<T>,
+    monoid_action: QuickMonoidAction<T, A, Op, Id, ActOp, ActId, ActApp>,
+) -> LinkCutTree<T, A, Op, Id, ActOp, ActId, ActApp>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+    ActOp: Fn(&A, &A) -> A,
+    ActId: Fn() -> A,
+    ActApp: Fn(&A, &T) -> T,
+{
+    let nodes = values
+        .into_iter()
+        .map(|value| Node {
+            left: NIL,
+            right: NIL,
+            parent: NIL,
+            data: ReversibleFold::new(value),
+        })
+        .collect();
+    LinkCutTree {
+        nodes,
+        monoid_action,
+    }
+}
+
+/// # Link-Cut Tree の構築 (作用付き、直接指定)
+///
+/// ## 例
+/// ```
+/// use link_cut_tree::link_cut_tree_new_with_action;
+/// // (和, 頂点数) に加算を作用させる
+/// let mut lct = link_cut_tree_new_with_action(
+///     vec![(1_i64, 1_i64); 5],
+///     |a: &(i64, i64), b: &(i64, i64)| (a.0 + b.0, a.1 + b.1),
+///     || (0, 0),
+///     |a: &i64, b: &i64| a + b,
+///     || 0,
+///     |a: &i64, x: &(i64, i64)| (x.0 + a * x.1, x.1),
+/// );
+/// lct.link(0, 1);
+/// lct.link(1, 2);
+/// lct.link(1, 3);
+/// lct.act(2, 3, 10);
+/// assert_eq!(lct.fold(0, 3), (23, 3));
+/// lct.cut(1, 3);
+/// assert_eq!(lct.fold(0, 2), (23, 3));
+/// assert!(!lct.connected(3, 4));
+/// ```
+#[inline]
+pub fn link_cut_tree_new_with_action<T, A, Op, Id, ActOp, ActId, ActApp>(
+    values: Vec<T>,
+    op: Op,
+    id: Id,
+    act_op: ActOp,
+    act_id: ActId,
+    act_app: ActApp,
+) -> LinkCutTree<T, A, Op, Id, ActOp, ActId, ActApp>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+    ActOp: Fn(&A, &A) -> A,
+    ActId: Fn() -> A,
+    ActApp: Fn(&A, &T) -> T,
+{
+    link_cut_tree_new_quick_monoid_action(
+        values,
+        QuickMonoidAction::new(op, id, act_op, act_id, act_app),
+    )
+}
+
+/// # Link-Cut Tree の構築 (作用付き、 `MonoidAction` による指定)
+#[inline]
+pub fn link_cut_tree_new_monoid_action<M>(values: Vec<M::T>) -> LinkCutTreeStatic<M::T, M::A>
+where
+    M: MonoidAction,
+    M::T: Clone,
+{
+    link_cut_tree_new_quick_monoid_action(values, M::as_quick())
+}
+
+/// # Link-Cut Tree の構築 (`QuickMonoid` による指定)
+///
+/// 作用は `()` となる。
+pub fn link_cut_tree_new_quick_monoid<T, Op, Id>(
+    values: Vec<T>,
+    monoid: QuickMonoid<T, Op, Id>,
+) -> LinkCutTree<T, (), Op, Id, fn(&(), &()), fn(), fn(&(), &T) -> T>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+{
+    link_cut_tree_new_quick_monoid_action(values, QuickMonoidActionTrivial::from(monoid))
+}
+
+/// # Link-Cut Tree の構築 (直接指定)
+///
+/// ## 例
+/// ```
+/// use link_cut_tree::link_cut_tree_new;
+/// // 文字列の連結 (非可換)
+/// let mut lct = link_cut_tree_new(
+///     ["a", "b", "c", "d"].iter().map(|s| s.to_string()).collect(),
+///     |a: &String, b: &String| a.clone() + b,
+///     String::new,
+/// );
+/// lct.link(0, 1);
+/// lct.link(1, 2);
+/// lct.link(3, 1);
+/// assert_eq!(lct.fold(0, 3), "abd");
+/// assert_eq!(lct.fold(2, 0), "cba");
+/// lct.set(1, "x".to_string());
+/// assert_eq!(lct.fold(3, 2), "dxc");
+/// assert_eq!(lct.lca(0, 2), Some(1));
+/// ```
+#[inline]
+pub fn link_cut_tree_new<T, Op, Id>(
+    values: Vec<T>,
+    op: Op,
+    id: Id,
+) -> LinkCutTree<T, (), Op, Id, fn(&(), &()), fn(), fn(&(), &T) -> T>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+{
+    link_cut_tree_new_quick_monoid(values, QuickMonoid::new(op, id))
+}
+
+/// # Link-Cut Tree の構築 (`Monoid` による指定)
+#[inline]
+pub fn link_cut_tree_new_monoid<T>(values: Vec<T>) -> LinkCutTreeStatic<T>
+where
+    T: Clone + Monoid,
+{
+    link_cut_tree_new_quick_monoid(values, T::as_quick())
+}
+
+#[cfg(test)]
+mod test;
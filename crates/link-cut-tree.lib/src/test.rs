@@ -0,0 +1,147 @@
+use crate::{link_cut_tree_new, link_cut_tree_new_with_action};
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+const P: u64 = 998_244_353;
+const B: u64 = 10;
+
+/// (ハッシュ, B^長さ, 1 + B + ... + B^(長さ-1))
+type Hash = (u64, u64, u64);
+
+fn op(a: &Hash, b: &Hash) -> Hash {
+    ((a.0 * b.1 + b.0) % P, a.1 * b.1 % P, (a.2 * b.1 + b.2) % P)
+}
+
+fn id() -> Hash {
+    (0, 1, 0)
+}
+
+fn single(x: u64) -> Hash {
+    (x, B, 1)
+}
+
+fn naive_path(adj: &[Vec<usize>], u: usize, v: usize) -> Option<Vec<usize>> {
+    let n = adj.len();
+    let mut prev = vec![usize::MAX; n];
+    let mut stack = vec![u];
+    prev[u] = u;
+    while let Some(x) = stack.pop() {
+        for &y in &adj[x] {
+            if prev[y] == usize::MAX {
+                prev[y] = x;
+                stack.push(y);
+            }
+        }
+    }
+    if prev[v] == usize::MAX {
+        return None;
+    }
+    let mut path = vec![v];
+    let mut x = v;
+    while x != u {
+        x = prev[x];
+        path.push(x);
+    }
+    path.reverse();
+    Some(path)
+}
+
+fn hash_of(values: &[u64], path: &[usize]) -> u64 {
+    path.iter().fold(0, |acc, &x| (acc * B + values[x]) % P)
+}
+
+#[test]
+fn test_random() {
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    for n in 1..=20 {
+        let mut values = (0..n).map(|_| rng.gen_range(0..B)).collect::<Vec<_>>();
+        let mut adj = vec![vec![]; n];
+        // 値の一括代入を作用とする
+        let mut lct = link_cut_tree_new_with_action(
+            values.iter().map(|&x| single(x)).collect(),
+            op,
+            id,
+            |a: &Option<u64>, b: &Option<u64>| a.or(*b),
+            || None,
+            |a: &Option<u64>, x: &Hash| match a {
+                Some(c) => (c * x.2 % P, x.1, x.2),
+                None => *x,
+            },
+        );
+        for _ in 0..300 {
+            let u = rng.gen_range(0..n);
+            let v = rng.gen_range(0..n);
+            let path = naive_path(&adj, u, v);
+            assert_eq!(lct.connected(u, v), path.is_some());
+            match rng.gen_range(0..6) {
+                0 => {
+                    if path.is_none() {
+                        lct.link(u, v);
+                        adj[u].push(v);
+                        adj[v].push(u);
+                    }
+                }
+                1 => {
+                    if !adj[u].is_empty() {
+                        let w = adj[u][rng.gen_range(0..adj[u].len())];
+                        lct.cut(w, u);
+                        adj[u].retain(|&x| x != w);
+                        adj[w].retain(|&x| x != u);
+                    }
+                }
+                2 => {
+                    if let Some(path) = path {
+                        assert_eq!(lct.fold(u, v).0, hash_of(&values, &path));
+                    }
+                }
+                3 => {
+                    if let Some(path) = path {
+                        let c = rng.gen_range(0..B);
+                        lct.act(u, v, Some(c));
+                        for &x in &path {
+                            values[x] = c;
+                        }
+                    }
+                }
+                4 => {
+                    let c = rng.gen_range(0..B);
+                    lct.set(u, single(c));
+                    values[u] = c;
+                }
+                _ => {
+                    assert_eq!(lct.get(u).0, values[u]);
+                    lct.evert(v);
+                    if let Some(path) = path {
+                        // v を根としたときの最小共通祖先は、 u から v へのパス上で
+                        // 3 頂点のパスの交わり
+                        let w = rng.gen_range(0..n);
+                        if let Some(path_w) = naive_path(&adj, w, v) {
+                            let lca = *path_w.iter().find(|x| path.contains(x)).unwrap();
+                            assert_eq!(lct.lca(u, w), Some(lca));
+                        }
+                    } else {
+                        assert_eq!(lct.lca(u, v), None);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_non_commutative() {
+    let mut lct = link_cut_tree_new((0..6).map(single).collect(), op, id);
+    for (u, v) in [(0, 1), (1, 2), (2, 3), (1, 4), (4, 5)] {
+        lct.link(u, v);
+    }
+    assert_eq!(lct.fold(3, 5).0, 32145);
+    assert_eq!(lct.fold(5, 3).0, 54123);
+    assert_eq!(lct.fold(0, 0).0, 0);
+    lct.evert(5);
+    assert_eq!(lct.lca(0, 3), Some(1));
+    assert_eq!(lct.lca(4, 3), Some(4));
+    // fold は根を変更する
+    assert_eq!(lct.fold(0, 3).0, 123);
+    assert_eq!(lct.lca(4, 3), Some(1));
+    assert_eq!(lct.root(3), 0);
+}
@@ -0,0 +1,246 @@
+use access_range::IntoAccessRange;
+use commutative_ring::CommutativeRing;
+use commutative_ring_as_additive_group::quick_group_by_add;
+use group::{group_to_quick, Group, QuickGroup};
+
+/// # Merge Sort Tree
+///
+/// セグメントツリーの各ノードに、区間の値をソートしたものとその累積を持つ。
+/// 値の更新はできない。
+/// 群は可換でなければならない。
+pub struct MergeSortTree<T, Op, Inv, Id>
+where
+    T: Clone + Ord,
+    Op: Fn(&T, &T) -> T,
+    Inv: Fn(&T) -> T,
+    Id: Fn() -> T,
+{
+    /// 各ノードの (値, 元の位置) を昇順に並べたもの
+    sorted: Vec<Vec<(T, usize)>>,
+    /// accum[node][i] は sorted[node][..i] の値の畳み込み
+    ///
+    /// `accum` クレートの `Accumulated` は群の関数を所有するので、ノードごとに持つと
+    /// 群の関数の複製 (`Clone` 制約やボックス化) が必要になる。
+    /// 1 つの群を共有するため、累積はここで直接計算する。
+    accum: Vec<Vec<T>>,
+    size: usize,
+    size_pow2: usize,
+    group: QuickGroup<T, Op, Inv, Id>,
+}
+
+impl<T, Op, Inv, Id> MergeSortTree<T, Op, Inv, Id>
+where
+    T: Clone + Ord,
+    Op: Fn(&T, &T) -> T,
+    Inv: Fn(&T) -> T,
+    Id: Fn() -> T,
+{
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// 区間の各ノードで、 `is_less` を満たす接頭辞の (長さ, 畳み込み) をまとめる
+    fn count_fold_less_by(
+        &self,
+        range: impl IntoAccessRange<usize>,
+        is_less: impl Fn(&(T, usize)) -> bool,
+    ) -> (usize, T) {
+        let range = range.into_access_range().into_range(self.size);
+        let mut count = 0;
+        let mut folded = self.group.id();
+        let mut add_node = |node: usize| {
+            let i = self.sorted[node].partition_point(&is_less);
+            count += i;
+            folded = self.group.op(&folded, &self.accum[node][i]);
+        };
+        let mut l = range.start + self.size_pow2;
+        let mut r = range.end + self.size_pow2;
+        while l < r {
+            if l % 2 == 1 {
+                add_node(l);
+                l += 1;
+            }
+            if r % 2 == 1 {
+                r -= 1;
+                add_node(r);
+            }
+            l /= 2;
+            r /= 2;
+        }
+        (count, folded)
+    }
+
+    /// # 区間内の `x` 未満の値の個数
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log^2 N)$
+    pub fn count_less(&self, range: impl IntoAccessRange<usize>, x: &T) -> usize {
+        self.count_fold_less_by(range, |(v, _)| v < x).0
+    }
+
+    /// # 区間内の `x` 未満の値の畳み込み
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log^2 N)$
+    pub fn fold_less(&self, range: impl IntoAccessRange<usize>, x: &T) -> T {
+        self.count_fold_less_by(range, |(v, _)| v < x).1
+    }
+
+    /// # 区間内の `lower` 以上 `upper` 未満の値の畳み込み
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log^2 N)$
+    pub fn fold_between(&self, range: impl IntoAccessRange<usize>, lower: &T, upper: &T) -> T {
+        if lower >= upper {
+            return self.group.id();
+        }
+        let range = range.into_access_range().into_range(self.size);
+        self.group.op(
+            &self.group.inv(&self.fold_less(range.clone(), lower)),
+            &self.fold_less(range, upper),
+        )
+    }
+
+    /// # 区間内の小さい方から `k` 個の値の畳み込み
+    ///
+    /// `k` が区間の長さより大きければ、区間全体の畳み込みを返す。
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log^3 N)$
+    pub fn fold_k_smallest(&self, range: impl IntoAccessRange<usize>, k: usize) -> T {
+        let range = range.into_access_range().into_range(self.size);
+        let root = &self.sorted[1];
+        // (値, 元の位置) の順序で区間内の k 番目の要素を根の列から二分探索する
+        let (mut lo, mut hi) = (0, root.len());
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            let count = self.count_fold_less_by(range.clone(), |e| e < &root[mid]).0;
+            if count >= k {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        if lo == root.len() {
+            self.count_fold_less_by(range, |_| true).1
+        } else {
+            self.count_fold_less_by(range, |e| e < &root[lo]).1
+        }
+    }
+}
+
+fn merge_sort_tree_quick<T, Op, Inv, Id>(
+    vec: Vec<T>,
+    group: QuickGroup<T, Op, Inv, Id>,
+) -> MergeSortTree<T, Op, Inv, Id>
+where
+    T: Clone + Ord,
+    Op: Fn(&T, &T) -> T,
+    Inv: Fn(&T) -> T,
+    Id: Fn() -> T,
+{
+    let size = vec.len();
+    let size_pow2 = size.next_power_of_two();
+    let mut sorted = vec![Vec::new(); 2 * size_pow2];
+    for (i, x) in vec.into_iter().enumerate() {
+        sorted[size_pow2 + i] = vec![(x, i)];
+    }
+    for node in (1..size_pow2).rev() {
+        let (left, right) = (&sorted[2 * node], &sorted[2 * node + 1]);
+        let mut merged = Vec::with_capacity(left.len() + right.len());
+        let (mut i, mut j) = (0, 0);
+        while i < left.len() || j < right.len() {
+            if j == right.len() || (i < left.len() && left[i] < right[j]) {
+                merged.push(left[i].clone());
+                i += 1;
+            } else {
+                merged.push(right[j].clone());
+                j += 1;
+            }
+        }
+        sorted[node] = merged;
+    }
+    let accum = sorted
+        .iter()
+        .map(|s| {
+            let mut a = Vec::with_capacity(s.len() + 1);
+            a.push(group.id());
+            for (x, _) in s {
+                a.push(group.op(a.last().unwrap(), x));
+            }
+            a
+        })
+        .collect();
+    MergeSortTree {
+        sorted,
+        accum,
+        size,
+        size_pow2,
+        group,
+    }
+}
+
+/// # Merge Sort Tree の構築 (加法による指定)
+///
+/// ## 計算量
+///
+/// $O(N \log N)$
+///
+/// ## 例
+/// ```
+/// use merge_sort_tree::merge_sort_tree_by_add;
+/// let mst = merge_sort_tree_by_add(vec![5_i64, 1, 4, 1, 5, 9, 2, 6]);
+/// assert_eq!(mst.count_less(.., &5), 4);
+/// assert_eq!(mst.fold_less(.., &5), 8);
+/// assert_eq!(mst.count_less(2..6, &5), 2);
+/// assert_eq!(mst.fold_less(2..6, &5), 5);
+/// assert_eq!(mst.fold_between(.., &2, &6), 16);
+/// // 3..8 の小さい方から 3 個の和 1 + 2 + 5
+/// assert_eq!(mst.fold_k_smallest(3..8, 3), 8);
+/// assert_eq!(mst.fold_k_smallest(3..8, 10), 23);
+/// ```
+#[inline]
+pub fn merge_sort_tree_by_add<T>(
+    vec: Vec<T>,
+) -> MergeSortTree<T, impl Fn(&T, &T) -> T, impl Fn(&T) -> T, impl Fn() -> T>
+where
+    T: CommutativeRing + Ord,
+{
+    merge_sort_tree_quick(vec, quick_group_by_add())
+}
+
+/// # Merge Sort Tree の構築 (`Group` による指定)
+#[inline]
+pub fn merge_sort_tree<T>(
+    vec: Vec<T>,
+) -> MergeSortTree<T, impl Fn(&T, &T) -> T, impl Fn(&T) -> T, impl Fn() -> T>
+where
+    T: Group + Ord,
+{
+    merge_sort_tree_quick(vec, group_to_quick())
+}
+
+/// # Merge Sort Tree の構築 (直接指定)
+#[inline]
+pub fn merge_sort_tree_by<T, Op, Inv, Id>(
+    vec: Vec<T>,
+    op: Op,
+    inv: Inv,
+    id: Id,
+) -> MergeSortTree<T, Op, Inv, Id>
+where
+    T: Clone + Ord,
+    Op: Fn(&T, &T) -> T,
+    Inv: Fn(&T) -> T,
+    Id: Fn() -> T,
+{
+    merge_sort_tree_quick(vec, QuickGroup::new(op, inv, id))
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,50 @@
+use crate::{merge_sort_tree_by, merge_sort_tree_by_add};
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+#[test]
+fn test_empty() {
+    let mst = merge_sort_tree_by_add(Vec::<i64>::new());
+    assert_eq!(mst.size(), 0);
+    assert_eq!(mst.count_less(.., &0), 0);
+    assert_eq!(mst.fold_less(.., &0), 0);
+    assert_eq!(mst.fold_k_smallest(.., 3), 0);
+}
+
+#[test]
+fn test_by_xor() {
+    let mst = merge_sort_tree_by(vec![3_u32, 5, 6, 3], |a, b| a ^ b, |a| *a, || 0);
+    assert_eq!(mst.fold_less(.., &6), 5);
+    assert_eq!(mst.fold_between(1.., &4, &7), 3);
+}
+
+#[test]
+fn test_random() {
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    for n in 1..=30 {
+        let v = (0..n).map(|_| rng.gen_range(-10..10)).collect::<Vec<i64>>();
+        let mst = merge_sort_tree_by_add(v.clone());
+        for _ in 0..100 {
+            let l = rng.gen_range(0..=n);
+            let r = rng.gen_range(l..=n);
+            let mut s = v[l..r].to_vec();
+            s.sort();
+            let x = rng.gen_range(-12..12);
+            let y = rng.gen_range(-12..12);
+            let k = rng.gen_range(0..=n + 1);
+            assert_eq!(
+                mst.count_less(l..r, &x),
+                s.iter().filter(|&&e| e < x).count()
+            );
+            assert_eq!(
+                mst.fold_less(l..r, &x),
+                s.iter().filter(|&&e| e < x).sum::<i64>()
+            );
+            assert_eq!(
+                mst.fold_between(l..r, &x, &y),
+                s.iter().filter(|&&e| x <= e && e < y).sum::<i64>()
+            );
+            assert_eq!(mst.fold_k_smallest(l..r, k), s.iter().take(k).sum::<i64>());
+        }
+    }
+}
@@ -0,0 +1,371 @@
+use access_range::IntoAccessRange;
+use ceil_log2::ceil_log2_usize;
+use monoid::{Monoid, QuickMonoid};
+
+/// # 永続セグメントツリーのバージョン
+///
+/// `PersistentSegmentTree` の各操作が返すハンドル。
+/// 作成元の木に対してのみ有効である。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PersistentSegmentTreeVersion(usize);
+
+/// # 永続セグメントツリー
+///
+/// 更新のたびに根から葉までの経路のみを複製し、以前のバージョンと構造を共有する。
+/// すべてのバージョンに対して畳み込みと探索ができる。
+pub struct PersistentSegmentTree<T, TFolded, TIntoFolded, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+    TIntoFolded: Fn(T) -> TFolded,
+{
+    values: Vec<T>,
+    /// 葉では使われない
+    children: Vec<(usize, usize)>,
+    initial: PersistentSegmentTreeVersion,
+    /// 論理的な長さ
+    size: usize,
+    /// size_pow2 == 1 << height
+    height: usize,
+    monoid: QuickMonoid<T, Op, Id>,
+    t_into_folded: TIntoFolded,
+}
+
+/// # 名前を付けられる永続セグメントツリーの型
+///
+/// 関数をすべて `fn` ポインタとして持つ `PersistentSegmentTree` 。
+pub type PersistentSegmentTreeStatic<T, TFolded = T> =
+    PersistentSegmentTree<T, TFolded, fn(T) -> TFolded, fn(&T, &T) -> T, fn() -> T>;
+
+impl<T, TFolded, TIntoFolded, Op, Id> PersistentSegmentTree<T, TFolded, TIntoFolded, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+    TIntoFolded: Fn(T) -> TFolded,
+{
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// 構築時のバージョン
+    #[inline]
+    pub fn initial_version(&self) -> PersistentSegmentTreeVersion {
+        self.initial
+    }
+
+    /// これまでに作られたノードの数
+    #[inline]
+    pub fn node_count(&self) -> usize {
+        self.values.len()
+    }
+
+    #[inline]
+    pub fn set_value_folded<TFolded2, TIntoFolded2>(
+        self,
+        value_folded: TIntoFolded2,
+    ) -> PersistentSegmentTree<T, TFolded2, TIntoFolded2, Op, Id>
+    where
+        TIntoFolded2: Fn(T) -> TFolded2,
+    {
+        PersistentSegmentTree {
+            values: self.values,
+            children: self.children,
+            initial: self.initial,
+            size: self.size,
+            height: self.height,
+            monoid: self.monoid,
+            t_into_folded: value_folded,
+        }
+    }
+
+    #[inline]
+    fn push_node(&mut self, value: T, children: (usize, usize)) -> usize {
+        self.values.push(value);
+        self.children.push(children);
+        self.values.len() - 1
+    }
+
+    /// # 取得
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log N)$
+    pub fn get(&self, version: PersistentSegmentTreeVersion, index: usize) -> T {
+        assert!(index < self.size, "index out of range: {}", index);
+        let mut node = version.0;
+        for h in (0..self.height).rev() {
+            let (left, right) = self.children[node];
+            node = if (index >> h) & 1 == 0 { left } else { right };
+        }
+        self.values[node].clone()
+    }
+
+    /// # 更新
+    ///
+    /// `version` の `index` 番目の値を `f` で更新した新しいバージョンを返す。
+    /// `version` 自体は変更されない。
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log N)$ 時間、 $O(\log N)$ 個のノードの追加
+    pub fn update<F>(
+        &mut self,
+        version: PersistentSegmentTreeVersion,
+        index: usize,
+        f: F,
+    ) -> PersistentSegmentTreeVersion
+    where
+        F: FnOnce(&T) -> T,
+    {
+        assert!(index < self.size, "index out of range: {}", index);
+        let mut path = Vec::with_capacity(self.height);
+        let mut node = version.0;
+        for h in (0..self.height).rev() {
+            path.push(node);
+            let (left, right) = self.children[node];
+            node = if (index >> h) & 1 == 0 { left } else { right };
+        }
+
+        let value = f(&self.values[node]);
+        let mut new_node = self.push_node(value, (usize::MAX, usize::MAX));
+        for (h, &old) in path.iter().rev().enumerate() {
+            let (left, right) = self.children[old];
+            let children = if (index >> h) & 1 == 0 {
+                (new_node, right)
+            } else {
+                (left, new_node)
+            };
+            let value = self
+                .monoid
+                .op(&self.values[children.0], &self.values[children.1]);
+            new_node = self.push_node(value, children);
+        }
+        PersistentSegmentTreeVersion(new_node)
+    }
+
+    /// # セット
+    ///
+    /// `version` の `index` 番目の値を `value` に置き換えた新しいバージョンを返す。
+    /// `version` 自体は変更されない。
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log N)$ 時間、 $O(\log N)$ 個のノードの追加
+    #[inline]
+    pub fn set(
+        &mut self,
+        version: PersistentSegmentTreeVersion,
+        index: usize,
+        value: T,
+    ) -> PersistentSegmentTreeVersion {
+        self.update(version, index, move |_| value)
+    }
+
+    fn fold_inner(&self, node: usize, nl: usize, nr: usize, l: usize, r: usize) -> T {
+        if r <= nl || nr <= l {
+            return self.monoid.id();
+        }
+        if l <= nl && nr <= r {
+            return self.values[node].clone();
+        }
+        let m = (nl + nr) / 2;
+        let (left, right) = self.children[node];
+        self.monoid.op(
+            &self.fold_inner(left, nl, m, l, r),
+            &self.fold_inner(right, m, nr, l, r),
+        )
+    }
+
+    /// # 区間の畳み込み
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log N)$
+    pub fn fold(
+        &self,
+        version: PersistentSegmentTreeVersion,
+        range: impl IntoAccessRange<usize>,
+    ) -> TFolded {
+        let range = range.into_access_range().into_range(self.size);
+        (self.t_into_folded)({
+            if range.start >= range.end {
+                self.monoid.id()
+            } else {
+                self.fold_inner(version.0, 0, 1 << self.height, range.start, range.end)
+            }
+        })
+    }
+
+    fn find_index_to_end_inner<F>(
+        &self,
+        node: usize,
+        nl: usize,
+        nr: usize,
+        l: usize,
+        acc: &mut T,
+        cond_fn: &F,
+    ) -> Option<usize>
+    where
+        F: Fn(TFolded, usize) -> bool,
+    {
+        if nr <= l {
+            return None;
+        }
+        if self.size <= nl {
+            return Some(self.size);
+        }
+        if l <= nl && nr <= self.size {
+            let next = self.monoid.op(acc, &self.values[node]);
+            if cond_fn((self.t_into_folded)(next.clone()), nr) {
+                *acc = next;
+                return None;
+            }
+            if nr - nl == 1 {
+                return Some(nl);
+            }
+        }
+        let m = (nl + nr) / 2;
+        let (left, right) = self.children[node];
+        self.find_index_to_end_inner(left, nl, m, l, acc, cond_fn)
+            .or_else(|| self.find_index_to_end_inner(right, m, nr, l, acc, cond_fn))
+    }
+
+    /// # 終端に向けて探す探索
+    ///
+    /// `SegmentTree::find_index_to_end` と同じだが、 `version` の時点の値に対して行う。
+    /// 単調な `cond_fn` と `l` について、 `cond_fn(fold(version, l..r), r)` を満たす
+    /// `l+1` 以上 `size()` 以下で最大の値 `r` を返す。
+    /// そのような値がなければ `l` を返す。
+    ///
+    /// # 計算量
+    ///
+    /// $O(\log N)$
+    ///
+    /// # 例
+    /// ```
+    /// use persistent_segment_tree::persistent_segment_tree_new;
+    /// let mut seg = persistent_segment_tree_new(vec![0; 5], |a: &i32, b: &i32| a + b, || 0);
+    /// let v0 = seg.initial_version();
+    /// let v1 = seg.set(v0, 1, 3);
+    /// let v2 = seg.set(v1, 3, 2);
+    /// assert_eq!(seg.find_index_to_end(v0, 0, |x, _| x < 3), 5);
+    /// assert_eq!(seg.find_index_to_end(v1, 0, |x, _| x < 3), 1);
+    /// assert_eq!(seg.find_index_to_end(v2, 2, |x, _| x < 2), 3);
+    /// ```
+    pub fn find_index_to_end<F>(
+        &self,
+        version: PersistentSegmentTreeVersion,
+        l: usize,
+        cond_fn: F,
+    ) -> usize
+    where
+        F: Fn(TFolded, usize) -> bool,
+    {
+        assert!(l <= self.size, "index out of range: {}", l);
+        let mut acc = self.monoid.id();
+        self.find_index_to_end_inner(version.0, 0, 1 << self.height, l, &mut acc, &cond_fn)
+            .unwrap_or(self.size)
+    }
+}
+
+/// # 永続セグメントツリーの構築 (`QuickMonoid` による指定)
+///
+/// ## 計算量
+///
+/// $O(N)$
+pub fn persistent_segment_tree_new_quick_monoid<T, Op, Id>(
+    vec: Vec<T>,
+    monoid: QuickMonoid<T, Op, Id>,
+) -> PersistentSegmentTree<T, T, fn(T) -> T, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+{
+    fn id_fn<T>(x: T) -> T {
+        x
+    }
+
+    let size = vec.len();
+    let height = ceil_log2_usize(size);
+    let len2 = 1 << height;
+    let mut values = Vec::with_capacity(len2 * 2 - 1);
+    let mut children = Vec::with_capacity(len2 * 2 - 1);
+    let mut level = Vec::with_capacity(len2);
+    for e in vec.into_iter().chain((size..len2).map(|_| monoid.id())) {
+        values.push(e);
+        children.push((usize::MAX, usize::MAX));
+        level.push(values.len() - 1);
+    }
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|c| {
+                values.push(monoid.op(&values[c[0]], &values[c[1]]));
+                children.push((c[0], c[1]));
+                values.len() - 1
+            })
+            .collect();
+    }
+    PersistentSegmentTree {
+        values,
+        children,
+        initial: PersistentSegmentTreeVersion(level[0]),
+        size,
+        height,
+        monoid,
+        t_into_folded: id_fn as fn(T) -> T,
+    }
+}
+
+/// # 永続セグメントツリーの構築 (直接指定)
+///
+/// ## 計算量
+///
+/// $O(N)$
+///
+/// ## 例
+/// ```
+/// use persistent_segment_tree::persistent_segment_tree_new;
+/// let mut seg = persistent_segment_tree_new(vec![1, 2, 3, 4], |a: &i32, b: &i32| a + b, || 0);
+/// let v0 = seg.initial_version();
+/// let v1 = seg.set(v0, 0, 10);
+/// let v2 = seg.update(v1, 3, |x| x * 2);
+/// assert_eq!(seg.fold(v0, ..), 10);
+/// assert_eq!(seg.fold(v1, ..), 19);
+/// assert_eq!(seg.fold(v2, ..), 23);
+/// assert_eq!(seg.fold(v2, 1..3), 5);
+/// assert_eq!(seg.get(v1, 3), 4);
+/// ```
+#[inline]
+pub fn persistent_segment_tree_new<T, Op, Id>(
+    vec: Vec<T>,
+    op: Op,
+    id: Id,
+) -> PersistentSegmentTree<T, T, fn(T) -> T, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+{
+    persistent_segment_tree_new_quick_monoid(vec, QuickMonoid::new(op, id))
+}
+
+/// # 永続セグメントツリーの構築 (`Monoid` による指定)
+///
+/// ## 計算量
+///
+/// $O(N)$
+#[inline]
+pub fn persistent_segment_tree_new_monoid<T>(vec: Vec<T>) -> PersistentSegmentTreeStatic<T>
+where
+    T: Clone + Monoid,
+{
+    persistent_segment_tree_new_quick_monoid(vec, T::as_quick())
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,75 @@
+use super::*;
+use rand::{self, Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+#[test]
+fn test_versions_random() {
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    for n in 1..=20 {
+        let v = (0..n).map(|_| rng.gen_range(0..100)).collect::<Vec<i64>>();
+        let mut seg = persistent_segment_tree_new(v.clone(), |a: &i64, b: &i64| a + b, || 0);
+        let mut versions = vec![(seg.initial_version(), v)];
+        for _ in 0..100 {
+            let (base, base_vec) = versions[rng.gen_range(0..versions.len())].clone();
+            let i = rng.gen_range(0..n);
+            let x = rng.gen_range(0..100);
+            let new = seg.set(base, i, x);
+            let mut new_vec = base_vec;
+            new_vec[i] = x;
+            versions.push((new, new_vec));
+
+            for (version, vec) in &versions {
+                let l = rng.gen_range(0..=n);
+                let r = rng.gen_range(l..=n);
+                assert_eq!(seg.fold(*version, l..r), vec[l..r].iter().sum::<i64>());
+
+                let i = rng.gen_range(0..n);
+                assert_eq!(seg.get(*version, i), vec[i]);
+
+                let bound = rng.gen_range(0..200);
+                let expected = (l..=n)
+                    .take_while(|&r| vec[l..r].iter().sum::<i64>() <= bound)
+                    .last()
+                    .unwrap();
+                assert_eq!(
+                    seg.find_index_to_end(*version, l, |x, _| x <= bound),
+                    expected
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_kth_smallest() {
+    // 値の出現回数を持つ永続セグメントツリーで、区間の k 番目に小さい値を求める
+    let a = vec![5, 1, 4, 1, 3, 2, 5, 0];
+    let mut seg = persistent_segment_tree_new_monoid(vec![Count(0); 6]).set_value_folded(|c| c.0);
+    let mut roots = vec![seg.initial_version()];
+    for &x in &a {
+        let last = *roots.last().unwrap();
+        roots.push(seg.update(last, x, |c| Count(c.0 + 1)));
+    }
+    let kth = |seg: &PersistentSegmentTree<_, _, _, _, _>, l: usize, r: usize, k: usize| {
+        let (lo, hi) = (roots[l], roots[r]);
+        seg.find_index_to_end(hi, 0, |_, end| {
+            seg.fold(hi, ..end) - seg.fold(lo, ..end) <= k
+        })
+    };
+    assert_eq!(kth(&seg, 0, 8, 0), 0);
+    assert_eq!(kth(&seg, 0, 8, 3), 2);
+    assert_eq!(kth(&seg, 1, 5, 0), 1);
+    assert_eq!(kth(&seg, 1, 5, 2), 3);
+    assert_eq!(kth(&seg, 1, 5, 3), 4);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Count(usize);
+impl Monoid for Count {
+    fn op(&self, other: &Self) -> Self {
+        Count(self.0 + other.0)
+    }
+    fn id() -> Self {
+        Count(0)
+    }
+}
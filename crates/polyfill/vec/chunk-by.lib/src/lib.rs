@@ -237,7 +237,7 @@ impl<T> VecChunkByPolyfill for [T] {
     /// assert_eq!(iter.next(), None);
     /// ```
     #[inline]
-    fn chunk_by<F>(&self, pred: F) -> ChunkBy<Self::T, F>
+    fn chunk_by<F>(&self, pred: F) -> ChunkBy<'_, Self::T, F>
     where
         F: FnMut(&Self::T, &Self::T) -> bool,
     {
@@ -0,0 +1,133 @@
+use monoid_action::QuickMonoidAction;
+use std::mem;
+
+/// # 反転と作用を遅延できる平衡二分木のノードの値
+///
+/// ノード自身の値と、部分木の畳み込みを順方向・逆方向の両方で持つ。
+/// 反転は 2 つの畳み込みを入れ替えるだけで済むので、非可換なモノイドでもよい。
+/// 作用は、列を反転させてから作用させても、作用させてから反転させても
+/// 同じ結果になる必要がある。
+///
+/// 子への伝播は [`ReversibleFold::push_to`] で行い、子の入れ替えだけ木の側で行う。
+#[derive(Clone, Debug)]
+pub struct ReversibleFold<T, A> {
+    pub value: T,
+    pub fold: T,
+    pub rev_fold: T,
+    /// 子に伝播していない反転
+    pub rev: bool,
+    /// 子に伝播していない作用
+    pub lazy: Option<A>,
+}
+
+impl<T: Clone, A> ReversibleFold<T, A> {
+    /// 子を持たないノード
+    pub fn new(value: T) -> Self {
+        Self {
+            fold: value.clone(),
+            rev_fold: value.clone(),
+            value,
+            rev: false,
+            lazy: None,
+        }
+    }
+
+    /// 左右の子と合わせた (順方向, 逆方向) の畳み込み
+    pub fn folds<Op, Id, ActOp, ActId, ActApp>(
+        &self,
+        m: &QuickMonoidAction<T, A, Op, Id, ActOp, ActId, ActApp>,
+        left: Option<&Self>,
+        right: Option<&Self>,
+    ) -> (T, T)
+    where
+        Op: Fn(&T, &T) -> T,
+        Id: Fn() -> T,
+        ActOp: Fn(&A, &A) -> A,
+        ActId: Fn() -> A,
+        ActApp: Fn(&A, &T) -> T,
+    {
+        let mut fold = self.value.clone();
+        let mut rev_fold = self.value.clone();
+        if let Some(l) = left {
+            fold = m.op(&l.fold, &fold);
+            rev_fold = m.op(&rev_fold, &l.rev_fold);
+        }
+        if let Some(r) = right {
+            fold = m.op(&fold, &r.fold);
+            rev_fold = m.op(&r.rev_fold, &rev_fold);
+        }
+        (fold, rev_fold)
+    }
+
+    /// 左右の子から畳み込みを計算し直す
+    pub fn update<Op, Id, ActOp, ActId, ActApp>(
+        &mut self,
+        m: &QuickMonoidAction<T, A, Op, Id, ActOp, ActId, ActApp>,
+        left: Option<&Self>,
+        right: Option<&Self>,
+    ) where
+        Op: Fn(&T, &T) -> T,
+        Id: Fn() -> T,
+        ActOp: Fn(&A, &A) -> A,
+        ActId: Fn() -> A,
+        ActApp: Fn(&A, &T) -> T,
+    {
+        (self.fold, self.rev_fold) = self.folds(m, left, right);
+    }
+
+    /// 部分木を反転させる
+    pub fn reverse(&mut self) {
+        mem::swap(&mut self.fold, &mut self.rev_fold);
+        self.rev = !self.rev;
+    }
+
+    /// 部分木に作用させる
+    pub fn apply<Op, Id, ActOp, ActId, ActApp>(
+        &mut self,
+        m: &QuickMonoidAction<T, A, Op, Id, ActOp, ActId, ActApp>,
+        a: &A,
+    ) where
+        Op: Fn(&T, &T) -> T,
+        Id: Fn() -> T,
+        ActOp: Fn(&A, &A) -> A,
+        ActId: Fn() -> A,
+        ActApp: Fn(&A, &T) -> T,
+    {
+        self.value = m.act_app(a, &self.value);
+        self.fold = m.act_app(a, &self.fold);
+        self.rev_fold = m.act_app(a, &self.rev_fold);
+        self.lazy = Some(match &self.lazy {
+            Some(lazy) => m.act_op(a, lazy),
+            None => m.act_op(a, &m.act_id()),
+        });
+    }
+
+    /// 伝播していない反転と作用を左右の子に伝播する
+    ///
+    /// 反転を伝播したときは `true` を返すので、木の側で左右の子を入れ替えること。
+    pub fn push_to<Op, Id, ActOp, ActId, ActApp>(
+        &mut self,
+        m: &QuickMonoidAction<T, A, Op, Id, ActOp, ActId, ActApp>,
+        left: Option<&mut Self>,
+        right: Option<&mut Self>,
+    ) -> bool
+    where
+        Op: Fn(&T, &T) -> T,
+        Id: Fn() -> T,
+        ActOp: Fn(&A, &A) -> A,
+        ActId: Fn() -> A,
+        ActApp: Fn(&A, &T) -> T,
+    {
+        let rev = mem::replace(&mut self.rev, false);
+        let lazy = self.lazy.take();
+        for c in [left, right].into_iter().flatten() {
+            if rev {
+                c.reverse();
+            }
+            if let Some(a) = &lazy {
+                c.apply(m, a);
+            }
+        }
+        rev
+    }
+}
@@ -0,0 +1,193 @@
+use access_range::IntoAccessRange;
+use monoid::Monoid;
+use segment_tree::{segment_tree_new_monoid, SegmentTreeStatic};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+/// 法 $2^{61}-1$
+pub const MOD: u64 = (1 << 61) - 1;
+
+#[inline]
+fn add_mod(a: u64, b: u64) -> u64 {
+    let c = a + b;
+    if c >= MOD {
+        c - MOD
+    } else {
+        c
+    }
+}
+
+#[inline]
+fn mul_mod(a: u64, b: u64) -> u64 {
+    let c = a as u128 * b as u128;
+    add_mod((c >> 61) as u64, (c as u64) & MOD)
+}
+
+/// # ローリングハッシュのモノイド
+///
+/// 列 $a_0, a_1, \dots, a_{n-1}$ に対して、法 $2^{61}-1$ で
+/// - `hash` $= \sum_i a_i B^{n-1-i}$
+/// - `rev_hash` $= \sum_i a_i B^i$ (逆順に並べた列のハッシュ)
+/// - `pow` $= B^n$
+///
+/// を持つ。 $B$ は葉を作るとき (`RollingHash::new`) に指定する。
+///
+/// ```
+/// use monoid::Monoid;
+/// use rolling_hash::RollingHash;
+/// let base = 1_000_003;
+/// let h = |s: &[u64]| {
+///     s.iter()
+///         .map(|&x| RollingHash::new(x, base))
+///         .fold(RollingHash::id(), |a, b| a.op(&b))
+/// };
+/// assert!(h(&[1, 2, 3]).same_as(&h(&[1, 2, 3])));
+/// assert!(!h(&[1, 2, 3]).same_as(&h(&[3, 2, 1])));
+/// assert!(!h(&[0]).same_as(&h(&[0, 0])));
+/// assert!(h(&[1, 2, 1]).is_palindrome());
+/// assert!(!h(&[1, 2, 2]).is_palindrome());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RollingHash {
+    pub hash: u64,
+    pub rev_hash: u64,
+    pub pow: u64,
+}
+
+impl RollingHash {
+    /// 長さ 1 の列 `[x]`
+    #[inline]
+    pub fn new(x: u64, base: u64) -> Self {
+        let x = x % MOD;
+        Self {
+            hash: x,
+            rev_hash: x,
+            pow: base % MOD,
+        }
+    }
+
+    /// 同じ列を表すか (ハッシュが衝突しない限り)
+    #[inline]
+    pub fn same_as(&self, other: &Self) -> bool {
+        self.hash == other.hash && self.pow == other.pow
+    }
+
+    /// 回文であるか (ハッシュが衝突しない限り)
+    #[inline]
+    pub fn is_palindrome(&self) -> bool {
+        self.hash == self.rev_hash
+    }
+}
+
+impl Monoid for RollingHash {
+    #[inline]
+    fn op(&self, other: &Self) -> Self {
+        Self {
+            hash: add_mod(mul_mod(self.hash, other.pow), other.hash),
+            rev_hash: add_mod(mul_mod(other.rev_hash, self.pow), self.rev_hash),
+            pow: mul_mod(self.pow, other.pow),
+        }
+    }
+    #[inline]
+    fn id() -> Self {
+        Self {
+            hash: 0,
+            rev_hash: 0,
+            pow: 1,
+        }
+    }
+}
+
+/// 実行ごとに異なる基数を返す
+pub fn rolling_hash_random_base() -> u64 {
+    let r = RandomState::new().build_hasher().finish();
+    // 小さすぎる基数を避ける
+    (1 << 32) + r % (MOD - (1 << 33))
+}
+
+/// # ローリングハッシュのセグメントツリー
+///
+/// 一点更新のもとで、部分列の比較や回文判定を $O(\log N)$ で行う。
+///
+/// ```
+/// use rolling_hash::rolling_hash_segment_tree_new;
+/// let s = b"abracadabra".iter().map(|&c| c as u64).collect();
+/// let mut seg = rolling_hash_segment_tree_new(s);
+/// assert!(seg.substr_eq(0..4, 7..11));
+/// assert!(!seg.substr_eq(0..4, 1..5));
+/// assert!(seg.is_palindrome(3..6));
+/// seg.set(5, b'x' as u64);
+/// assert!(!seg.is_palindrome(3..6));
+/// assert!(seg.is_palindrome(5..6));
+/// assert_eq!(seg.get(5), b'x' as u64);
+/// ```
+pub struct RollingHashSegmentTree {
+    seg: SegmentTreeStatic<RollingHash>,
+    base: u64,
+}
+
+impl RollingHashSegmentTree {
+    pub fn new(vec: Vec<u64>, base: u64) -> Self {
+        Self {
+            seg: segment_tree_new_monoid(
+                vec.into_iter().map(|x| RollingHash::new(x, base)).collect(),
+            ),
+            base,
+        }
+    }
+
+    #[inline]
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.seg.size()
+    }
+
+    /// `MOD` で割った余りを返す
+    #[inline]
+    pub fn get(&self, index: usize) -> u64 {
+        self.seg.get(index).hash
+    }
+
+    #[inline]
+    pub fn set(&mut self, index: usize, x: u64) {
+        self.seg.set(index, RollingHash::new(x, self.base));
+    }
+
+    #[inline]
+    pub fn hash(&self, range: impl IntoAccessRange<usize>) -> RollingHash {
+        self.seg.fold(range)
+    }
+
+    /// 2 つの部分列が等しいか (ハッシュが衝突しない限り)
+    #[inline]
+    pub fn substr_eq(
+        &self,
+        range1: impl IntoAccessRange<usize>,
+        range2: impl IntoAccessRange<usize>,
+    ) -> bool {
+        self.hash(range1).same_as(&self.hash(range2))
+    }
+
+    /// 部分列が回文であるか (ハッシュが衝突しない限り)
+    #[inline]
+    pub fn is_palindrome(&self, range: impl IntoAccessRange<usize>) -> bool {
+        self.hash(range).is_palindrome()
+    }
+}
+
+/// 基数を `rolling_hash_random_base()` で決めて構築する
+pub fn rolling_hash_segment_tree_new(vec: Vec<u64>) -> RollingHashSegmentTree {
+    RollingHashSegmentTree::new(vec, rolling_hash_random_base())
+}
+
+/// 基数を指定して構築する
+pub fn rolling_hash_segment_tree_new_with_base(vec: Vec<u64>, base: u64) -> RollingHashSegmentTree {
+    RollingHashSegmentTree::new(vec, base)
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,37 @@
+use crate::{rolling_hash_segment_tree_new, RollingHash, MOD};
+use monoid::Monoid;
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+#[test]
+fn test_mul_mod_large() {
+    let a = RollingHash::new(MOD - 1, MOD - 1);
+    let b = a.op(&a);
+    // (-1) * (-1) + (-1) = 0
+    assert_eq!(b.hash, 0);
+    assert_eq!(b.pow, 1);
+}
+
+#[test]
+fn test_random() {
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    for n in 1..=20 {
+        let mut v = (0..n).map(|_| rng.gen_range(0..3)).collect::<Vec<u64>>();
+        let mut seg = rolling_hash_segment_tree_new(v.clone());
+        for _ in 0..100 {
+            let i = rng.gen_range(0..n);
+            let x = rng.gen_range(0..3);
+            v[i] = x;
+            seg.set(i, x);
+
+            let l1 = rng.gen_range(0..=n);
+            let r1 = rng.gen_range(l1..=n);
+            let l2 = rng.gen_range(0..=n);
+            let r2 = rng.gen_range(l2..=n);
+            assert_eq!(seg.substr_eq(l1..r1, l2..r2), v[l1..r1] == v[l2..r2]);
+            let s = &v[l1..r1];
+            assert_eq!(seg.is_palindrome(l1..r1), s.iter().eq(s.iter().rev()));
+            assert_eq!(seg.get(i), x);
+        }
+    }
+}
@@ -0,0 +1,484 @@
+use access_range::IntoAccessRange;
+use commutative_ring::CommutativeRing;
+use int::{Int, UnsignedInt};
+use min_max_count::{MaxCount, MinCount};
+use shrink_provider::{NoShrink, ShrinkProvider};
+
+#[derive(Clone, Debug)]
+struct Node<T, USize: UnsignedInt> {
+    sum: T,
+    max: MaxCount<T, USize>,
+    /// 2番目に大きい値 (すべて等しければ `None`)
+    max2: Option<T>,
+    min: MinCount<T, USize>,
+    /// 2番目に小さい値 (すべて等しければ `None`)
+    min2: Option<T>,
+    len: USize,
+    lazy_add: T,
+}
+
+fn max_second<T: Ord>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+fn min_second<T: Ord>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// # Segment Tree Beats
+///
+/// 区間 chmin 、区間 chmax 、区間加算と、区間和・区間最小値・区間最大値の取得を行う。
+///
+/// 各ノードに最大値、 2 番目の最大値、最大値の個数 (最小値も同様) を持ち、
+/// 作用がノードの最大値のみに影響する場合に限って遅延させる。
+///
+/// ## 計算量
+///
+/// 償却 $O(\log^2 N)$
+pub struct SegmentTreeBeats<T, SP>
+where
+    T: Int,
+    SP: ShrinkProvider,
+{
+    /// 1-indexed で、ノード k の子は 2k, 2k+1
+    tree: Vec<Node<T, SP::USize>>,
+    size: usize,
+    t_mul_usize: Box<dyn Fn(&T, SP::USize) -> T>,
+}
+
+impl<T, SP> SegmentTreeBeats<T, SP>
+where
+    T: Int,
+    SP: ShrinkProvider,
+{
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    fn leaf(&self, x: T, len: SP::USize) -> Node<T, SP::USize> {
+        Node {
+            sum: (self.t_mul_usize)(&x, len),
+            max: MaxCount { max: x, count: len },
+            max2: None,
+            min: MinCount { min: x, count: len },
+            min2: None,
+            len,
+            lazy_add: T::zero(),
+        }
+    }
+
+    fn pull(&mut self, k: usize) {
+        let (l, r) = (&self.tree[2 * k], &self.tree[2 * k + 1]);
+        let sum = l.sum + r.sum;
+        let len = l.len + r.len;
+        let (max, max2) = match l.max.max.cmp(&r.max.max) {
+            std::cmp::Ordering::Less => (r.max.clone(), max_second(Some(l.max.max), r.max2)),
+            std::cmp::Ordering::Greater => (l.max.clone(), max_second(l.max2, Some(r.max.max))),
+            std::cmp::Ordering::Equal => (
+                MaxCount {
+                    max: l.max.max,
+                    count: l.max.count + r.max.count,
+                },
+                max_second(l.max2, r.max2),
+            ),
+        };
+        let (min, min2) = match l.min.min.cmp(&r.min.min) {
+            std::cmp::Ordering::Less => (l.min.clone(), min_second(l.min2, Some(r.min.min))),
+            std::cmp::Ordering::Greater => (r.min.clone(), min_second(Some(l.min.min), r.min2)),
+            std::cmp::Ordering::Equal => (
+                MinCount {
+                    min: l.min.min,
+                    count: l.min.count + r.min.count,
+                },
+                min_second(l.min2, r.min2),
+            ),
+        };
+        let node = &mut self.tree[k];
+        node.sum = sum;
+        node.len = len;
+        node.max = max;
+        node.max2 = max2;
+        node.min = min;
+        node.min2 = min2;
+    }
+
+    fn apply_add(&mut self, k: usize, x: T) {
+        let sum_diff = (self.t_mul_usize)(&x, self.tree[k].len);
+        let node = &mut self.tree[k];
+        node.sum += sum_diff;
+        node.max.max += x;
+        if let Some(max2) = &mut node.max2 {
+            *max2 += x;
+        }
+        node.min.min += x;
+        if let Some(min2) = &mut node.min2 {
+            *min2 += x;
+        }
+        node.lazy_add += x;
+    }
+
+    /// `max2 < x < max` のときのみ呼ばれる (`max2` がなければ `x < max`)
+    fn apply_chmin(&mut self, k: usize, x: T) {
+        let node = &self.tree[k];
+        let sum_diff = (self.t_mul_usize)(&(x - node.max.max), node.max.count);
+        let node = &mut self.tree[k];
+        node.sum += sum_diff;
+        if node.min.min == node.max.max {
+            node.min.min = x;
+        } else if node.min2 == Some(node.max.max) {
+            node.min2 = Some(x);
+        }
+        node.max.max = x;
+    }
+
+    /// `min < x < min2` のときのみ呼ばれる (`min2` がなければ `min < x`)
+    fn apply_chmax(&mut self, k: usize, x: T) {
+        let node = &self.tree[k];
+        let sum_diff = (self.t_mul_usize)(&(x - node.min.min), node.min.count);
+        let node = &mut self.tree[k];
+        node.sum += sum_diff;
+        if node.max.max == node.min.min {
+            node.max.max = x;
+        } else if node.max2 == Some(node.min.min) {
+            node.max2 = Some(x);
+        }
+        node.min.min = x;
+    }
+
+    fn push(&mut self, k: usize) {
+        let add = std::mem::replace(&mut self.tree[k].lazy_add, T::zero());
+        let (max, min) = (self.tree[k].max.max, self.tree[k].min.min);
+        for c in [2 * k, 2 * k + 1] {
+            if add != T::zero() {
+                self.apply_add(c, add);
+            }
+            if self.tree[c].max.max > max {
+                self.apply_chmin(c, max);
+            }
+            if self.tree[c].min.min < min {
+                self.apply_chmax(c, min);
+            }
+        }
+    }
+
+    fn chmin_inner(&mut self, k: usize, nl: usize, nr: usize, l: usize, r: usize, x: T) {
+        if r <= nl || nr <= l || self.tree[k].max.max <= x {
+            return;
+        }
+        if l <= nl && nr <= r && !matches!(self.tree[k].max2, Some(max2) if max2 >= x) {
+            self.apply_chmin(k, x);
+            return;
+        }
+        let nm = (nl + nr) / 2;
+        self.push(k);
+        self.chmin_inner(2 * k, nl, nm, l, r, x);
+        self.chmin_inner(2 * k + 1, nm, nr, l, r, x);
+        self.pull(k);
+    }
+
+    fn chmax_inner(&mut self, k: usize, nl: usize, nr: usize, l: usize, r: usize, x: T) {
+        if r <= nl || nr <= l || self.tree[k].min.min >= x {
+            return;
+        }
+        if l <= nl && nr <= r && !matches!(self.tree[k].min2, Some(min2) if min2 <= x) {
+            self.apply_chmax(k, x);
+            return;
+        }
+        let nm = (nl + nr) / 2;
+        self.push(k);
+        self.chmax_inner(2 * k, nl, nm, l, r, x);
+        self.chmax_inner(2 * k + 1, nm, nr, l, r, x);
+        self.pull(k);
+    }
+
+    fn add_inner(&mut self, k: usize, nl: usize, nr: usize, l: usize, r: usize, x: T) {
+        if r <= nl || nr <= l {
+            return;
+        }
+        if l <= nl && nr <= r {
+            self.apply_add(k, x);
+            return;
+        }
+        let nm = (nl + nr) / 2;
+        self.push(k);
+        self.add_inner(2 * k, nl, nm, l, r, x);
+        self.add_inner(2 * k + 1, nm, nr, l, r, x);
+        self.pull(k);
+    }
+
+    /// 区間を覆うノードを左から順に `nodes` に集める
+    fn collect_nodes(
+        &mut self,
+        k: usize,
+        nl: usize,
+        nr: usize,
+        l: usize,
+        r: usize,
+        nodes: &mut Vec<usize>,
+    ) {
+        if r <= nl || nr <= l {
+            return;
+        }
+        if l <= nl && nr <= r {
+            nodes.push(k);
+            return;
+        }
+        let nm = (nl + nr) / 2;
+        self.push(k);
+        self.collect_nodes(2 * k, nl, nm, l, r, nodes);
+        self.collect_nodes(2 * k + 1, nm, nr, l, r, nodes);
+    }
+
+    fn fold_with<U>(
+        &mut self,
+        range: impl IntoAccessRange<usize>,
+        get: impl Fn(&Node<T, SP::USize>) -> U,
+        merge: impl Fn(U, U) -> U,
+    ) -> Option<U> {
+        let range = range.into_access_range().into_range(self.size);
+        if range.start >= range.end {
+            return None;
+        }
+        let mut nodes = Vec::new();
+        self.collect_nodes(1, 0, self.size, range.start, range.end, &mut nodes);
+        nodes.into_iter().map(|k| get(&self.tree[k])).reduce(merge)
+    }
+
+    /// # 区間 chmin
+    ///
+    /// 区間の各値 `v` を `min(v, x)` に置き換える。
+    pub fn chmin(&mut self, range: impl IntoAccessRange<usize>, x: T) {
+        let range = range.into_access_range().into_range(self.size);
+        if range.start >= range.end {
+            return;
+        }
+        self.chmin_inner(1, 0, self.size, range.start, range.end, x);
+    }
+
+    /// # 区間 chmax
+    ///
+    /// 区間の各値 `v` を `max(v, x)` に置き換える。
+    pub fn chmax(&mut self, range: impl IntoAccessRange<usize>, x: T) {
+        let range = range.into_access_range().into_range(self.size);
+        if range.start >= range.end {
+            return;
+        }
+        self.chmax_inner(1, 0, self.size, range.start, range.end, x);
+    }
+
+    /// # 区間加算
+    pub fn add(&mut self, range: impl IntoAccessRange<usize>, x: T) {
+        let range = range.into_access_range().into_range(self.size);
+        if range.start >= range.end {
+            return;
+        }
+        self.add_inner(1, 0, self.size, range.start, range.end, x);
+    }
+
+    /// # 区間和
+    pub fn fold_sum(&mut self, range: impl IntoAccessRange<usize>) -> T {
+        self.fold_with(range, |node| node.sum, |a, b| a + b)
+            .unwrap_or_else(T::zero)
+    }
+
+    /// # 区間最小値とその個数
+    pub fn fold_min(&mut self, range: impl IntoAccessRange<usize>) -> MinCount<T, SP::USize> {
+        self.fold_with(
+            range,
+            |node| node.min.clone(),
+            |a, b| match a.min.cmp(&b.min) {
+                std::cmp::Ordering::Less => a,
+                std::cmp::Ordering::Greater => b,
+                std::cmp::Ordering::Equal => MinCount {
+                    min: a.min,
+                    count: a.count + b.count,
+                },
+            },
+        )
+        .unwrap_or_else(|| MinCount {
+            min: T::max_exists(),
+            count: SP::USize::zero(),
+        })
+    }
+
+    /// # 区間最大値とその個数
+    pub fn fold_max(&mut self, range: impl IntoAccessRange<usize>) -> MaxCount<T, SP::USize> {
+        self.fold_with(
+            range,
+            |node| node.max.clone(),
+            |a, b| match a.max.cmp(&b.max) {
+                std::cmp::Ordering::Greater => a,
+                std::cmp::Ordering::Less => b,
+                std::cmp::Ordering::Equal => MaxCount {
+                    max: a.max,
+                    count: a.count + b.count,
+                },
+            },
+        )
+        .unwrap_or_else(|| MaxCount {
+            max: T::min_exists(),
+            count: SP::USize::zero(),
+        })
+    }
+
+    /// # 取得
+    #[inline]
+    pub fn get(&mut self, index: usize) -> T {
+        assert!(index < self.size, "index out of range: {}", index);
+        self.fold_max(index).max
+    }
+}
+
+pub struct LazySegmentTreeChminChmaxAddBuilder<T, SP>
+where
+    T: Int,
+    SP: ShrinkProvider,
+{
+    vec: Vec<T>,
+    t_mul_usize: Option<Box<dyn Fn(&T, SP::USize) -> T>>,
+    sp: SP,
+}
+
+impl<T, SP> LazySegmentTreeChminChmaxAddBuilder<T, SP>
+where
+    T: Int + 'static,
+    SP: ShrinkProvider,
+{
+    pub fn new(vec: Vec<T>, sp: SP) -> Self {
+        Self {
+            vec,
+            t_mul_usize: None,
+            sp,
+        }
+    }
+
+    pub fn set_all_auto(self) -> Self
+    where
+        T: TryFrom<SP::USize>,
+    {
+        self.set_mul_usize_auto()
+    }
+
+    pub fn set_mul_usize(mut self, t_mul_usize: impl Fn(&T, SP::USize) -> T + 'static) -> Self {
+        self.t_mul_usize = Some(Box::new(t_mul_usize));
+        self
+    }
+    pub fn set_mul_usize_auto(self) -> Self
+    where
+        T: TryFrom<SP::USize>,
+    {
+        self.set_mul_usize_by_mul()
+    }
+    pub fn set_mul_usize_by_mul(self) -> Self
+    where
+        T: TryFrom<SP::USize>,
+    {
+        self.set_mul_usize(|x, len| {
+            let len = T::try_from(len).unwrap_or_else(|_| {
+                panic!(
+                    "{}: Couldn't convert USize {} to T",
+                    stringify!(LazySegmentTreeChminChmaxAddBuilder),
+                    len,
+                )
+            });
+            *x * len
+        })
+    }
+
+    pub fn build(self) -> SegmentTreeBeats<T, SP> {
+        self.t_mul_usize.as_ref().or_else(|| {
+            panic!(
+                "{}: mul_usize is not set",
+                stringify!(LazySegmentTreeChminChmaxAddBuilder)
+            )
+        });
+        unsafe { self.build_unchecked() }
+    }
+
+    /// ## Safety
+    /// - すべてのメソッドが設定されていること
+    pub unsafe fn build_unchecked(self) -> SegmentTreeBeats<T, SP> {
+        let t_mul_usize = unsafe { self.t_mul_usize.unwrap_unchecked() };
+        let size = self.vec.len();
+        let mut seg = SegmentTreeBeats {
+            tree: Vec::new(),
+            size,
+            t_mul_usize,
+        };
+        let empty = seg.leaf(T::zero(), SP::USize::zero());
+        seg.tree = vec![empty; 4 * size.max(1)];
+
+        fn build<T: Int, SP: ShrinkProvider>(
+            seg: &mut SegmentTreeBeats<T, SP>,
+            vec: &[T],
+            sp: &SP,
+            k: usize,
+            nl: usize,
+            nr: usize,
+        ) {
+            if nr - nl == 1 {
+                seg.tree[k] = seg.leaf(vec[nl], sp.size_of_shrinked(nl));
+                return;
+            }
+            let nm = (nl + nr) / 2;
+            build(seg, vec, sp, 2 * k, nl, nm);
+            build(seg, vec, sp, 2 * k + 1, nm, nr);
+            seg.pull(k);
+        }
+        if size > 0 {
+            build(&mut seg, &self.vec, &self.sp, 1, 0, size);
+        }
+        seg
+    }
+}
+
+pub fn lazy_segment_tree_builder_chmin_chmax_add_shrinkable<T, SP>(
+    vec: Vec<T>,
+    sp: SP,
+) -> LazySegmentTreeChminChmaxAddBuilder<T, SP>
+where
+    T: Int + 'static,
+    SP: ShrinkProvider,
+{
+    LazySegmentTreeChminChmaxAddBuilder::new(vec, sp)
+}
+
+pub fn lazy_segment_tree_builder_chmin_chmax_add<T>(
+    vec: Vec<T>,
+) -> LazySegmentTreeChminChmaxAddBuilder<T, NoShrink>
+where
+    T: Int + 'static,
+{
+    LazySegmentTreeChminChmaxAddBuilder::new(vec, NoShrink)
+}
+
+pub fn lazy_segment_tree_new_chmin_chmax_add_shrinkable<T, SP>(
+    vec: Vec<T>,
+    sp: SP,
+) -> SegmentTreeBeats<T, SP>
+where
+    T: Int + TryFrom<SP::USize> + 'static,
+    SP: ShrinkProvider,
+{
+    let b = lazy_segment_tree_builder_chmin_chmax_add_shrinkable(vec, sp).set_all_auto();
+    unsafe { b.build_unchecked() }
+}
+
+#[doc = include_str!("../doc_new.md")]
+pub fn lazy_segment_tree_new_chmin_chmax_add<T>(vec: Vec<T>) -> SegmentTreeBeats<T, NoShrink>
+where
+    T: Int + 'static,
+{
+    let b = lazy_segment_tree_builder_chmin_chmax_add(vec).set_all_auto();
+    unsafe { b.build_unchecked() }
+}
+
+#[cfg(test)]
+mod test;
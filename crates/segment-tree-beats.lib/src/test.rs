@@ -0,0 +1,115 @@
+use super::*;
+use rand::{self, Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+#[test]
+fn test_random() {
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    for n in 0..=30 {
+        let mut v = (0..n)
+            .map(|_| rng.gen_range(-100..100))
+            .collect::<Vec<i64>>();
+        let mut seg = lazy_segment_tree_new_chmin_chmax_add(v.clone());
+        assert_eq!(seg.size(), n);
+        for _ in 0..300 {
+            let l = rng.gen_range(0..=n);
+            let r = rng.gen_range(l..=n);
+            let x = rng.gen_range(-100..100);
+            match rng.gen_range(0..3) {
+                0 => {
+                    seg.chmin(l..r, x);
+                    for e in &mut v[l..r] {
+                        *e = (*e).min(x);
+                    }
+                }
+                1 => {
+                    seg.chmax(l..r, x);
+                    for e in &mut v[l..r] {
+                        *e = (*e).max(x);
+                    }
+                }
+                _ => {
+                    let x = x / 10;
+                    seg.add(l..r, x);
+                    for e in &mut v[l..r] {
+                        *e += x;
+                    }
+                }
+            }
+
+            let l = rng.gen_range(0..=n);
+            let r = rng.gen_range(l..=n);
+            let s = &v[l..r];
+            assert_eq!(seg.fold_sum(l..r), s.iter().sum::<i64>());
+            let min = s.iter().copied().min().unwrap_or(i64::MAX);
+            assert_eq!(
+                seg.fold_min(l..r),
+                MinCount {
+                    min,
+                    count: s.iter().filter(|&&e| e == min).count(),
+                }
+            );
+            let max = s.iter().copied().max().unwrap_or(i64::MIN);
+            assert_eq!(
+                seg.fold_max(l..r),
+                MaxCount {
+                    max,
+                    count: s.iter().filter(|&&e| e == max).count(),
+                }
+            );
+            if n > 0 {
+                let i = rng.gen_range(0..n);
+                assert_eq!(seg.get(i), v[i]);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_shrinkable() {
+    #[derive(Clone)]
+    struct Weights(Vec<u32>);
+    impl ShrinkProvider for Weights {
+        type USize = u32;
+        fn size_of_shrinked(&self, index: usize) -> u32 {
+            self.0[index]
+        }
+    }
+    let mut seg = lazy_segment_tree_builder_chmin_chmax_add_shrinkable(
+        vec![3_i32, 1, 4],
+        Weights(vec![2, 5, 1]),
+    )
+    .set_mul_usize(|x, len| x * len as i32)
+    .build();
+    assert_eq!(seg.fold_sum(..), 3 * 2 + 5 + 4);
+    seg.chmin(.., 2);
+    assert_eq!(seg.fold_sum(..), 2 * 2 + 5 + 2);
+    assert_eq!(seg.fold_max(..), MaxCount { max: 2, count: 3 });
+    assert_eq!(seg.fold_min(..), MinCount { min: 1, count: 5 });
+}
+
+#[test]
+fn test_extreme_values() {
+    // 型の最小値を含んでいても、2 番目の値を番兵と取り違えない
+    let mut seg = lazy_segment_tree_new_chmin_chmax_add(vec![i64::MIN, 5, 7]);
+    assert_eq!(
+        seg.fold_min(..),
+        MinCount {
+            min: i64::MIN,
+            count: 1,
+        }
+    );
+    seg.chmax(1.., 6);
+    assert_eq!(seg.fold_max(..2), MaxCount { max: 6, count: 1 });
+    seg.add(..1, 1);
+    assert_eq!(
+        seg.fold_min(..),
+        MinCount {
+            min: i64::MIN + 1,
+            count: 1,
+        }
+    );
+    seg.chmin(.., 6);
+    assert_eq!(seg.fold_max(..), MaxCount { max: 6, count: 2 });
+    assert_eq!(seg.get(0), i64::MIN + 1);
+}
@@ -28,6 +28,55 @@ where
     phantom: PhantomData<TSetter>,
 }
 
+/// # 名前を付けられるセグメントツリーの型
+///
+/// 関数をすべて `fn` ポインタとして持つ `SegmentTree` 。
+/// `seg_type!` は `impl Fn` を含むため構造体のフィールドなどには書けないが、こちらは書ける。
+///
+/// ## 例
+///
+/// ```
+/// use segment_tree::{segment_tree_new_static, SegmentTreeStatic};
+///
+/// struct Solver {
+///     seg: SegmentTreeStatic<i64>,
+/// }
+///
+/// let mut solver = Solver {
+///     seg: segment_tree_new_static(vec![1, 4, 2, 3], |a, b| a + b, || 0),
+/// };
+/// solver.seg.set(0, 5);
+/// assert_eq!(solver.seg.fold(..), 14);
+/// ```
+pub type SegmentTreeStatic<T, TFolded = T, TGetter = T, TSetter = T> = SegmentTree<
+    T,
+    TFolded,
+    TGetter,
+    TSetter,
+    fn(T) -> TFolded,
+    fn(T, usize) -> TGetter,
+    fn(TSetter, usize) -> T,
+    fn(&T, &T) -> T,
+    fn() -> T,
+>;
+
+/// # 関数をボックス化したセグメントツリーの型
+///
+/// 関数をすべて `Box<dyn Fn>` として持つ `SegmentTree` 。
+/// キャプチャを持つ関数で構築したもの (ユーティリティのビルダーなど) も
+/// [`SegmentTree::into_dyn`] によってこの型に変換すれば名前を付けられる。
+pub type SegmentTreeDyn<T, TFolded = T, TGetter = T, TSetter = T> = SegmentTree<
+    T,
+    TFolded,
+    TGetter,
+    TSetter,
+    Box<dyn Fn(T) -> TFolded>,
+    Box<dyn Fn(T, usize) -> TGetter>,
+    Box<dyn Fn(TSetter, usize) -> T>,
+    Box<dyn Fn(&T, &T) -> T>,
+    Box<dyn Fn() -> T>,
+>;
+
 /// # セグメントツリーの構築 (モノイドの直接指定)
 ///
 /// 初期リスト、演算子、単位元の順で指定する。
@@ -51,6 +100,36 @@ pub fn segment_tree_new<T>(
     op: impl Fn(&T, &T) -> T,
     id: impl Fn() -> T,
 ) -> seg_type!(T = T) {
+    segment_tree_new_internal(vec, op, id)
+}
+
+/// # セグメントツリーの構築 (`fn` ポインタによる指定)
+///
+/// `segment_tree_new` と同じだが、名前を付けられる `SegmentTreeStatic` を返す。
+/// 演算子と単位元にはキャプチャを持たないクロージャか関数を指定する。
+///
+/// ## 計算量
+///
+/// $O(N)$
+#[inline]
+pub fn segment_tree_new_static<T>(
+    vec: Vec<T>,
+    op: fn(&T, &T) -> T,
+    id: fn() -> T,
+) -> SegmentTreeStatic<T> {
+    segment_tree_new_internal(vec, op, id)
+}
+
+#[inline]
+fn segment_tree_new_internal<T, Op, Id>(
+    vec: Vec<T>,
+    op: Op,
+    id: Id,
+) -> SegmentTree<T, T, T, T, fn(T) -> T, fn(T, usize) -> T, fn(T, usize) -> T, Op, Id>
+where
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+{
     fn id_fn<T>(x: T) -> T {
         x
     }
@@ -70,9 +149,9 @@ pub fn segment_tree_new<T>(
             size: len,
             size_pow2: 0,
 
-            t_into_folded: id_fn,
-            t_into_getter: id_fn_idx,
-            t_from_setter: id_fn_idx,
+            t_into_folded: id_fn as fn(T) -> T,
+            t_into_getter: id_fn_idx as fn(T, usize) -> T,
+            t_from_setter: id_fn_idx as fn(T, usize) -> T,
 
             phantom: PhantomData,
         };
@@ -104,9 +183,9 @@ pub fn segment_tree_new<T>(
         size: len,
         size_pow2: len2,
 
-        t_into_folded: id_fn,
-        t_into_getter: id_fn_idx,
-        t_from_setter: id_fn_idx,
+        t_into_folded: id_fn as fn(T) -> T,
+        t_into_getter: id_fn_idx as fn(T, usize) -> T,
+        t_from_setter: id_fn_idx as fn(T, usize) -> T,
 
         phantom: PhantomData,
     }
@@ -122,15 +201,13 @@ where
     TFromSetter: Fn(TSetter, /* index */ usize) -> T,
 {
     #[inline(always)]
-    pub fn set_value_folded<TFolded2>(
+    pub fn set_value_folded<TFolded2, TIntoFolded2>(
         self,
-        t_into_folded: impl Fn(T) -> TFolded2,
-    ) -> seg_type!(
-        T = T,
-        TFolded = TFolded2,
-        TGetter = TGetter,
-        TSetter = TSetter,
-    ) {
+        t_into_folded: TIntoFolded2,
+    ) -> SegmentTree<T, TFolded2, TGetter, TSetter, TIntoFolded2, TIntoGetter, TFromSetter, Op, Id>
+    where
+        TIntoFolded2: Fn(T) -> TFolded2,
+    {
         SegmentTree {
             monoid: self.monoid,
 
@@ -147,15 +224,13 @@ where
     }
 
     #[inline(always)]
-    pub fn set_value_getter<TGetter2>(
+    pub fn set_value_getter<TGetter2, TIntoGetter2>(
         self,
-        t_into_getter: impl Fn(T, usize) -> TGetter2,
-    ) -> seg_type!(
-        T = T,
-        TFolded = TFolded,
-        TGetter = TGetter2,
-        TSetter = TSetter,
-    ) {
+        t_into_getter: TIntoGetter2,
+    ) -> SegmentTree<T, TFolded, TGetter2, TSetter, TIntoFolded, TIntoGetter2, TFromSetter, Op, Id>
+    where
+        TIntoGetter2: Fn(T, usize) -> TGetter2,
+    {
         SegmentTree {
             monoid: self.monoid,
 
@@ -172,15 +247,13 @@ where
     }
 
     #[inline(always)]
-    pub fn set_value_setter<TSetter2>(
+    pub fn set_value_setter<TSetter2, TFromSetter2>(
         self,
-        t_from_setter: impl Fn(TSetter2, usize) -> T,
-    ) -> seg_type!(
-        T = T,
-        TFolded = TFolded,
-        TGetter = TGetter,
-        TSetter = TSetter2,
-    ) {
+        t_from_setter: TFromSetter2,
+    ) -> SegmentTree<T, TFolded, TGetter, TSetter2, TIntoFolded, TIntoGetter, TFromSetter2, Op, Id>
+    where
+        TFromSetter2: Fn(TSetter2, usize) -> T,
+    {
         SegmentTree {
             monoid: self.monoid,
 
@@ -201,6 +274,33 @@ where
         self.size
     }
 
+    /// # 関数のボックス化
+    ///
+    /// 名前を付けられる [`SegmentTreeDyn`] に変換する。
+    pub fn into_dyn(self) -> SegmentTreeDyn<T, TFolded, TGetter, TSetter>
+    where
+        TIntoFolded: 'static,
+        TIntoGetter: 'static,
+        TFromSetter: 'static,
+        Op: 'static,
+        Id: 'static,
+    {
+        let (op, id) = self.monoid.into_parts();
+        SegmentTree {
+            monoid: QuickMonoid::new(Box::new(op), Box::new(id)),
+
+            tree: self.tree,
+            size: self.size,
+            size_pow2: self.size_pow2,
+
+            t_into_folded: Box::new(self.t_into_folded),
+            t_into_getter: Box::new(self.t_into_getter),
+            t_from_setter: Box::new(self.t_from_setter),
+
+            phantom: PhantomData,
+        }
+    }
+
     #[inline(always)]
     fn parent_tree_index(&self, tree_index: usize) -> usize {
         debug_assert!(1 <= tree_index && tree_index <= self.tree.len());
@@ -327,20 +427,22 @@ where
         if index >= self.size {
             panic!("index out of range: {}", index);
         }
-        let mut index = self.leaf_of(index);
-        *unsafe { self.tree.get_unchecked_mut(index) } = (self.t_from_setter)(
+        let mut tree_index = self.leaf_of(index);
+        *unsafe { self.tree.get_unchecked_mut(tree_index) } = (self.t_from_setter)(
             update_fn((self.t_into_getter)(
-                self.monoid
-                    .op(unsafe { self.tree.get_unchecked(index) }, &self.monoid.id()),
+                self.monoid.op(
+                    unsafe { self.tree.get_unchecked(tree_index) },
+                    &self.monoid.id(),
+                ),
                 index,
             ))
             .into(),
             index,
         );
-        while index > self.root_node() {
-            index = self.parent_tree_index(index);
-            let (left, right) = self.children_indices(index);
-            *unsafe { self.tree.get_unchecked_mut(index) } = self
+        while tree_index > self.root_node() {
+            tree_index = self.parent_tree_index(tree_index);
+            let (left, right) = self.children_indices(tree_index);
+            *unsafe { self.tree.get_unchecked_mut(tree_index) } = self
                 .monoid
                 .op(unsafe { self.tree.get_unchecked(left) }, unsafe {
                     self.tree.get_unchecked(right)
@@ -572,7 +674,7 @@ where
             }
 
             // ノード `cur` が左側の子である間、 `cur` を親に置き換える
-            while cur % 2 == 0 {
+            while cur & 1 == 0 {
                 cur = self.parent_tree_index(cur);
                 cur_len *= 2;
             }
@@ -607,11 +709,16 @@ where
     }
 }
 
-pub fn segment_tree_new_monoid<T, Op, Id>(vec: Vec<T>) -> seg_type!(T = T)
+/// # セグメントツリーの構築 (モノイドの型による指定)
+///
+/// ## 計算量
+///
+/// $O(N)$
+pub fn segment_tree_new_monoid<T>(vec: Vec<T>) -> SegmentTreeStatic<T>
 where
     T: Monoid,
 {
-    segment_tree_new(vec, T::op, T::id)
+    segment_tree_new_static(vec, T::op, T::id)
 }
 
 #[cfg(test)]
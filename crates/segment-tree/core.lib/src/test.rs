@@ -1,3 +1,4 @@
+#![allow(clippy::manual_div_ceil)]
 use crate::segment_tree_new;
 use rand::{self, Rng, SeedableRng};
 use rand_xoshiro::Xoshiro256PlusPlus;
@@ -229,3 +230,110 @@ fn test_take_right() {
     seg.set(5, 0);
     assert_eq!(seg.fold(..7), 8);
 }
+
+#[test]
+fn test_static_in_struct_field() {
+    use crate::{segment_tree_new_monoid, segment_tree_new_static, SegmentTreeStatic};
+    use monoid::Monoid;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Concat(String);
+    impl Monoid for Concat {
+        fn op(&self, other: &Self) -> Self {
+            Concat(format!("{}{}", self.0, other.0))
+        }
+        fn id() -> Self {
+            Concat(String::new())
+        }
+    }
+
+    struct Solver {
+        sum: SegmentTreeStatic<i64>,
+        concat: SegmentTreeStatic<Concat>,
+    }
+    impl Solver {
+        fn sum_mut(&mut self) -> &mut SegmentTreeStatic<i64> {
+            &mut self.sum
+        }
+    }
+
+    let mut solver = Solver {
+        sum: segment_tree_new_static(vec![1, 4, 2, 3], |a, b| a + b, || 0),
+        concat: segment_tree_new_monoid(
+            ["a", "b", "c"]
+                .iter()
+                .map(|s| Concat(s.to_string()))
+                .collect(),
+        ),
+    };
+    solver.sum_mut().set(1, 10);
+    assert_eq!(solver.sum.fold(..), 16);
+    assert_eq!(solver.concat.fold(1..), Concat("bc".to_string()));
+}
+
+#[test]
+fn test_dyn_in_struct_field() {
+    use crate::{segment_tree_new, SegmentTreeDyn};
+
+    struct Solver {
+        seg: SegmentTreeDyn<i64, i64, i64, i64>,
+    }
+
+    let modulo = 7;
+    let mut solver = Solver {
+        seg: segment_tree_new(vec![1, 4, 2, 3], move |a, b| (a + b) % modulo, || 0)
+            .set_value_getter(move |x, i| x * 10 + i as i64)
+            .into_dyn(),
+    };
+    assert_eq!(solver.seg.fold(..), 3);
+    solver.seg.set(1, 5);
+    assert_eq!(solver.seg.fold(..), 4);
+    assert_eq!(solver.seg.get(1), 51);
+}
+
+#[test]
+fn test_setter_getter_index() {
+    let mut seg = segment_tree_new(vec![(0, 0), (0, 1), (0, 2)], |a, b| *a.max(b), || (0, 0))
+        .set_value_getter(|(x, i), j| {
+            assert_eq!(i, j);
+            x
+        })
+        .set_value_setter(|x, i| (x, i));
+    seg.set(1, 5);
+    assert_eq!(seg.fold(..), (5, 1));
+    seg.update(2, |x| x + 7);
+    assert_eq!(seg.fold(..), (7, 2));
+    assert_eq!(seg.get(2), 7);
+}
+
+#[test]
+fn test_max_subarray_random() {
+    use segment_tree_util_max_subarray::segment_tree_new_max_subarray_with_boundaries;
+
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    for n in 1..=20 {
+        let mut v = (0..n).map(|_| rng.gen_range(-5..5)).collect::<Vec<i64>>();
+        let mut seg = segment_tree_new_max_subarray_with_boundaries(v.clone());
+        for _ in 0..100 {
+            let i = rng.gen_range(0..n);
+            let x = rng.gen_range(-5..5);
+            v[i] = x;
+            seg.set(i, x);
+
+            let l = rng.gen_range(0..n);
+            let r = rng.gen_range(l + 1..=n);
+            let res = seg.fold(l..r);
+            let best = (l..r)
+                .flat_map(|a| (a + 1..=r).map(move |b| (a, b)))
+                .map(|(a, b)| v[a..b].iter().sum::<i64>())
+                .max()
+                .unwrap();
+            assert_eq!(res.best, best);
+            assert_eq!(v[res.best_range.clone()].iter().sum::<i64>(), best);
+            assert_eq!(v[l..res.prefix_end].iter().sum::<i64>(), res.prefix);
+            assert_eq!(v[res.suffix_start..r].iter().sum::<i64>(), res.suffix);
+            assert_eq!(v[l..r].iter().sum::<i64>(), res.total);
+            assert_eq!(res.range, l..r);
+        }
+    }
+}
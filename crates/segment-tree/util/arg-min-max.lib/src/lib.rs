@@ -0,0 +1,233 @@
+use arg_min_max::{ArgMax, ArgMin, ArgPosition};
+use max_exists::MaxExists;
+use min_exists::MinExists;
+use segment_tree::{segment_tree_new, segment_tree_new_static, SegmentTree, SegmentTreeStatic};
+use segment_tree_util_type::seg_type;
+use std::cmp;
+
+macro_rules! f {
+    (
+        $builder_name:ident,
+        $fn_new:ident,
+        $fn_builder:ident,
+        $min_or_max:ident,
+        $arg_min_or_max:ident,
+        $max_or_min_exists:ident,
+        $max_or_min_exists_method:ident,
+        $set_max_or_min_exists:ident,
+        $set_max_or_min_exists_auto:ident,
+        $greater_or_less:ident,
+        $less_or_greater:ident,
+        $doc_fn_new:expr $(,)?
+    ) => {
+        pub struct $builder_name<T> {
+            vec: Vec<T>,
+            t_ord: Option<Box<dyn Fn(&T, &T) -> cmp::Ordering>>,
+            t_max_or_min_exists: Option<Box<dyn Fn() -> T>>,
+            position: ArgPosition,
+        }
+
+        impl<T> $builder_name<T>
+        where
+            T: Clone,
+        {
+            pub fn new(vec: Vec<T>) -> Self {
+                Self {
+                    vec,
+                    t_ord: None,
+                    t_max_or_min_exists: None,
+                    position: ArgPosition::Leftmost,
+                }
+            }
+
+            pub fn set_all_auto(self) -> Self
+            where
+                T: cmp::PartialOrd + $max_or_min_exists,
+            {
+                self.set_ord_auto().$set_max_or_min_exists_auto()
+            }
+
+            pub fn set_ord(mut self, t_ord: impl Fn(&T, &T) -> cmp::Ordering + 'static) -> Self {
+                self.t_ord = Some(Box::new(t_ord));
+                self
+            }
+            pub fn set_ord_auto(self) -> Self
+            where
+                T: cmp::PartialOrd,
+            {
+                self.set_ord_by_partial_ord()
+            }
+            pub fn set_ord_by_ord(self) -> Self
+            where
+                T: cmp::Ord,
+            {
+                self.set_ord(|a, b| a.cmp(b))
+            }
+            pub fn set_ord_by_partial_ord(self) -> Self
+            where
+                T: cmp::PartialOrd,
+            {
+                self.set_ord(|a, b| {
+                    a.partial_cmp(b).unwrap_or_else(|| {
+                        panic!("{}: partial_cmp should be total", stringify!($builder_name))
+                    })
+                })
+            }
+
+            pub fn $set_max_or_min_exists(
+                mut self,
+                t_max_or_min_exists: impl Fn() -> T + 'static,
+            ) -> Self {
+                self.t_max_or_min_exists = Some(Box::new(t_max_or_min_exists));
+                self
+            }
+            pub fn $set_max_or_min_exists_auto(self) -> Self
+            where
+                T: $max_or_min_exists,
+            {
+                self.$set_max_or_min_exists(|| T::$max_or_min_exists_method())
+            }
+
+            /// 値が等しい位置が複数あるときにどれを選ぶか (既定では最も左)
+            pub fn set_position(mut self, position: ArgPosition) -> Self {
+                self.position = position;
+                self
+            }
+
+            pub fn build(
+                self,
+            ) -> seg_type!(
+                   T = (T, usize),
+                   TFolded = $arg_min_or_max<T, usize>,
+                   TGetter = T,
+                   TSetter = T,
+               ) {
+                self.t_max_or_min_exists.as_ref().or_else(|| {
+                    panic!(
+                        "{}: max_or_min_exists is not set",
+                        stringify!($builder_name)
+                    )
+                });
+                self.t_ord
+                    .as_ref()
+                    .or_else(|| panic!("{}: ord is not set", stringify!($builder_name)));
+                unsafe { self.build_unchecked() }
+            }
+
+            /// ## Safety
+            /// - すべてのメソッドが設定されていること
+            pub unsafe fn build_unchecked(
+                self,
+            ) -> seg_type!(
+                   T = (T, usize),
+                   TFolded = $arg_min_or_max<T, usize>,
+                   TGetter = T,
+                   TSetter = T,
+               ) {
+                let t_max_or_min_exists = unsafe { self.t_max_or_min_exists.unwrap_unchecked() };
+                let t_ord = unsafe { self.t_ord.unwrap_unchecked() };
+                let position = self.position;
+                segment_tree_new(
+                    self.vec
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, x)| (x, i))
+                        .collect(),
+                    move |a: &(T, usize), b: &(T, usize)| match t_ord(&a.0, &b.0) {
+                        cmp::Ordering::$less_or_greater => a.clone(),
+                        cmp::Ordering::$greater_or_less => b.clone(),
+                        cmp::Ordering::Equal => (a.0.clone(), position.select(a.1, b.1)),
+                    },
+                    move || (t_max_or_min_exists(), usize::MAX),
+                )
+                .set_value_folded(|(x, i)| $arg_min_or_max {
+                    $min_or_max: x,
+                    index: i,
+                })
+                .set_value_getter(|(x, _), _| x)
+                .set_value_setter(|x, i| (x, i))
+            }
+        }
+
+        pub fn $fn_builder<T>(vec: Vec<T>) -> $builder_name<T>
+        where
+            T: Clone,
+        {
+            $builder_name::new(vec)
+        }
+
+        #[doc = include_str!($doc_fn_new)]
+        pub fn $fn_new<T>(
+            vec: Vec<T>,
+        ) -> SegmentTreeStatic<(T, usize), $arg_min_or_max<T, usize>, T, T>
+        where
+            T: Clone + cmp::PartialOrd + $max_or_min_exists,
+        {
+            fn op<T: Clone + cmp::PartialOrd>(a: &(T, usize), b: &(T, usize)) -> (T, usize) {
+                match a.0.partial_cmp(&b.0) {
+                    Some(cmp::Ordering::$less_or_greater) => a.clone(),
+                    Some(cmp::Ordering::$greater_or_less) => b.clone(),
+                    Some(cmp::Ordering::Equal) => {
+                        (a.0.clone(), ArgPosition::Leftmost.select(a.1, b.1))
+                    }
+                    None => panic!("{}: partial_cmp should be total", stringify!($builder_name)),
+                }
+            }
+            fn id<T: $max_or_min_exists>() -> (T, usize) {
+                (T::$max_or_min_exists_method(), usize::MAX)
+            }
+            fn into_folded<T>((x, i): (T, usize)) -> $arg_min_or_max<T, usize> {
+                $arg_min_or_max {
+                    $min_or_max: x,
+                    index: i,
+                }
+            }
+            fn into_getter<T>((x, _): (T, usize), _: usize) -> T {
+                x
+            }
+            fn from_setter<T>(x: T, i: usize) -> (T, usize) {
+                (x, i)
+            }
+            segment_tree_new_static(
+                vec.into_iter().enumerate().map(|(i, x)| (x, i)).collect(),
+                op::<T>,
+                id::<T>,
+            )
+            .set_value_folded(into_folded::<T> as fn(_) -> _)
+            .set_value_getter(into_getter::<T> as fn(_, _) -> _)
+            .set_value_setter(from_setter::<T> as fn(_, _) -> _)
+        }
+    };
+}
+
+f!(
+    SegmentTreeArgMinBuilder,
+    segment_tree_new_arg_min,
+    segment_tree_builder_arg_min,
+    min,
+    ArgMin,
+    MaxExists,
+    max_exists,
+    set_max_exists,
+    set_max_exists_auto,
+    Greater,
+    Less,
+    "../doc_new_arg_min.md",
+);
+f!(
+    SegmentTreeArgMaxBuilder,
+    segment_tree_new_arg_max,
+    segment_tree_builder_arg_max,
+    max,
+    ArgMax,
+    MinExists,
+    min_exists,
+    set_min_exists,
+    set_min_exists_auto,
+    Less,
+    Greater,
+    "../doc_new_arg_max.md",
+);
+
+#[cfg(test)]
+mod test;
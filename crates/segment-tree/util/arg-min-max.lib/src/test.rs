@@ -0,0 +1,53 @@
+use super::*;
+use arg_min_max::{ArgMax, ArgMin, ArgPosition};
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+#[test]
+fn test_set_update_random() {
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    for n in 1..=20 {
+        let mut v = (0..n).map(|_| rng.gen_range(-3..3)).collect::<Vec<i64>>();
+        let mut min = segment_tree_new_arg_min(v.clone());
+        let mut max = segment_tree_builder_arg_max(v.clone())
+            .set_all_auto()
+            .set_position(ArgPosition::Rightmost)
+            .build();
+        for _ in 0..100 {
+            // 更新後も各葉の位置が保たれていることを、返される位置で確かめる
+            let i = rng.gen_range(0..n);
+            let x = rng.gen_range(-3..3);
+            if rng.gen_bool(0.5) {
+                v[i] = x;
+                min.set(i, x);
+                max.set(i, x);
+            } else {
+                v[i] += x;
+                min.update(i, |e| e + x);
+                max.update(i, |e| e + x);
+            }
+            assert_eq!(min.get(i), v[i]);
+            assert_eq!(max.get(i), v[i]);
+
+            let l = rng.gen_range(0..n);
+            let r = rng.gen_range(l + 1..=n);
+            let s = &v[l..r];
+            let m = *s.iter().min().unwrap();
+            assert_eq!(
+                min.fold(l..r),
+                ArgMin {
+                    min: m,
+                    index: l + s.iter().position(|&e| e == m).unwrap(),
+                }
+            );
+            let m = *s.iter().max().unwrap();
+            assert_eq!(
+                max.fold(l..r),
+                ArgMax {
+                    max: m,
+                    index: l + s.iter().rposition(|&e| e == m).unwrap(),
+                }
+            );
+        }
+    }
+}
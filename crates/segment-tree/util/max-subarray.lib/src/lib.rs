@@ -0,0 +1,328 @@
+use commutative_ring_ord::CommutativeRingOrd;
+use min_exists::MinExists;
+use segment_tree::{segment_tree_new, SegmentTree};
+use segment_tree_util_type::seg_type;
+use std::cmp;
+use std::ops::{self, Range};
+use std::rc::Rc;
+
+/// 区間の総和と、空でない接頭辞・接尾辞・連続部分列の和の最大値
+///
+/// 和が等しいときは、より左にあるものを選ぶ。
+/// 空の区間では `range` が空となり、最大値はすべて `min_exists` となる。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MaxSubarray<T> {
+    /// この値が表す区間
+    pub range: Range<usize>,
+    pub total: T,
+    /// 接頭辞 `range.start..prefix_end` の和
+    pub prefix: T,
+    pub prefix_end: usize,
+    /// 接尾辞 `suffix_start..range.end` の和
+    pub suffix: T,
+    pub suffix_start: usize,
+    /// 連続部分列 `best_range` の和
+    pub best: T,
+    pub best_range: Range<usize>,
+}
+
+pub struct SegmentTreeMaxSubarrayBuilder<T> {
+    vec: Vec<T>,
+    t_add: Option<Box<dyn Fn(&T, &T) -> T>>,
+    t_zero: Option<Box<dyn Fn() -> T>>,
+    t_ord: Option<Box<dyn Fn(&T, &T) -> cmp::Ordering>>,
+    t_min_exists: Option<Box<dyn Fn() -> T>>,
+}
+
+impl<T> SegmentTreeMaxSubarrayBuilder<T>
+where
+    T: Clone,
+{
+    pub fn new(vec: Vec<T>) -> Self {
+        Self {
+            vec,
+            t_add: None,
+            t_zero: None,
+            t_ord: None,
+            t_min_exists: None,
+        }
+    }
+
+    pub fn set_all_auto(self) -> Self
+    where
+        T: CommutativeRingOrd + MinExists,
+    {
+        self.set_add_auto()
+            .set_zero_auto()
+            .set_ord_auto()
+            .set_min_exists_auto()
+    }
+
+    pub fn set_add(mut self, t_add: impl Fn(&T, &T) -> T + 'static) -> Self {
+        self.t_add = Some(Box::new(t_add));
+        self
+    }
+    pub fn set_add_auto(self) -> Self
+    where
+        T: CommutativeRingOrd,
+    {
+        self.set_add_by_commutative_ring_ord()
+    }
+    pub fn set_add_by_commutative_ring_ord(self) -> Self
+    where
+        T: CommutativeRingOrd,
+    {
+        self.set_add(|a, b| a.add(b))
+    }
+    pub fn set_add_by_add(self) -> Self
+    where
+        T: ops::Add<Output = T>,
+    {
+        self.set_add(|a, b| a.clone() + b.clone())
+    }
+
+    pub fn set_zero(mut self, t_zero: impl Fn() -> T + 'static) -> Self {
+        self.t_zero = Some(Box::new(t_zero));
+        self
+    }
+    pub fn set_zero_auto(self) -> Self
+    where
+        T: CommutativeRingOrd,
+    {
+        self.set_zero_by_commutative_ring_ord()
+    }
+    pub fn set_zero_by_commutative_ring_ord(self) -> Self
+    where
+        T: CommutativeRingOrd,
+    {
+        self.set_zero(|| T::zero())
+    }
+    pub fn set_zero_by_default(self) -> Self
+    where
+        T: Default,
+    {
+        self.set_zero(|| T::default())
+    }
+
+    pub fn set_ord(mut self, t_ord: impl Fn(&T, &T) -> cmp::Ordering + 'static) -> Self {
+        self.t_ord = Some(Box::new(t_ord));
+        self
+    }
+    pub fn set_ord_auto(self) -> Self
+    where
+        T: cmp::Ord,
+    {
+        self.set_ord_by_ord()
+    }
+    pub fn set_ord_by_ord(self) -> Self
+    where
+        T: cmp::Ord,
+    {
+        self.set_ord(|a, b| a.cmp(b))
+    }
+    pub fn set_ord_by_partial_ord(self) -> Self
+    where
+        T: cmp::PartialOrd,
+    {
+        self.set_ord(|a, b| {
+            a.partial_cmp(b).unwrap_or_else(|| {
+                panic!(
+                    "{}: partial_cmp should be total",
+                    stringify!(SegmentTreeMaxSubarrayBuilder)
+                )
+            })
+        })
+    }
+
+    pub fn set_min_exists(mut self, t_min_exists: impl Fn() -> T + 'static) -> Self {
+        self.t_min_exists = Some(Box::new(t_min_exists));
+        self
+    }
+    pub fn set_min_exists_auto(self) -> Self
+    where
+        T: MinExists,
+    {
+        self.set_min_exists(|| T::min_exists())
+    }
+
+    fn check(&self) {
+        self.t_add.as_ref().or_else(|| {
+            panic!(
+                "{}: add is not set",
+                stringify!(SegmentTreeMaxSubarrayBuilder)
+            )
+        });
+        self.t_zero.as_ref().or_else(|| {
+            panic!(
+                "{}: zero is not set",
+                stringify!(SegmentTreeMaxSubarrayBuilder)
+            )
+        });
+        self.t_ord.as_ref().or_else(|| {
+            panic!(
+                "{}: ord is not set",
+                stringify!(SegmentTreeMaxSubarrayBuilder)
+            )
+        });
+        self.t_min_exists.as_ref().or_else(|| {
+            panic!(
+                "{}: min_exists is not set",
+                stringify!(SegmentTreeMaxSubarrayBuilder)
+            )
+        });
+    }
+
+    /// 連続部分列の和の最大値のみを返す
+    pub fn build(self) -> seg_type!(T = MaxSubarray<T>, TFolded = T, TGetter = T, TSetter = T) {
+        self.check();
+        unsafe { self.build_unchecked() }
+    }
+    /// ## Safety
+    /// - すべてのメソッドが設定されていること
+    pub unsafe fn build_unchecked(
+        self,
+    ) -> seg_type!(T = MaxSubarray<T>, TFolded = T, TGetter = T, TSetter = T) {
+        self.build_with_boundaries_unchecked()
+            .set_value_folded(|t| t.best)
+    }
+
+    /// 区間の情報をすべて返す
+    pub fn build_with_boundaries(
+        self,
+    ) -> seg_type!(
+           T = MaxSubarray<T>,
+           TFolded = MaxSubarray<T>,
+           TGetter = T,
+           TSetter = T,
+       ) {
+        self.check();
+        unsafe { self.build_with_boundaries_unchecked() }
+    }
+    /// ## Safety
+    /// - すべてのメソッドが設定されていること
+    pub unsafe fn build_with_boundaries_unchecked(
+        self,
+    ) -> seg_type!(
+           T = MaxSubarray<T>,
+           TFolded = MaxSubarray<T>,
+           TGetter = T,
+           TSetter = T,
+       ) {
+        let t_add = unsafe { self.t_add.unwrap_unchecked() };
+        let t_zero = unsafe { self.t_zero.unwrap_unchecked() };
+        let t_ord = unsafe { self.t_ord.unwrap_unchecked() };
+        let t_min_exists = unsafe { self.t_min_exists.unwrap_unchecked() };
+        let gt = move |a: &T, b: &T| t_ord(a, b) == cmp::Ordering::Greater;
+        let leaf = |x: T, i: usize| MaxSubarray {
+            range: i..i + 1,
+            total: x.clone(),
+            prefix: x.clone(),
+            prefix_end: i + 1,
+            suffix: x.clone(),
+            suffix_start: i,
+            best: x,
+            best_range: i..i + 1,
+        };
+        segment_tree_new(
+            self.vec
+                .into_iter()
+                .enumerate()
+                .map(|(i, x)| leaf(x, i))
+                .collect(),
+            move |a: &MaxSubarray<T>, b: &MaxSubarray<T>| {
+                // 単位元は空の区間として、演算の前に取り除く
+                if a.range.is_empty() {
+                    return b.clone();
+                }
+                if b.range.is_empty() {
+                    return a.clone();
+                }
+                let (prefix, prefix_end) = {
+                    let x = t_add(&a.total, &b.prefix);
+                    if gt(&x, &a.prefix) {
+                        (x, b.prefix_end)
+                    } else {
+                        (a.prefix.clone(), a.prefix_end)
+                    }
+                };
+                let (suffix, suffix_start) = {
+                    let x = t_add(&a.suffix, &b.total);
+                    if gt(&b.suffix, &x) {
+                        (b.suffix.clone(), b.suffix_start)
+                    } else {
+                        (x, a.suffix_start)
+                    }
+                };
+                let (mut best, mut best_range) = (a.best.clone(), a.best_range.clone());
+                let x = t_add(&a.suffix, &b.prefix);
+                if gt(&x, &best) {
+                    (best, best_range) = (x, a.suffix_start..b.prefix_end);
+                }
+                if gt(&b.best, &best) {
+                    (best, best_range) = (b.best.clone(), b.best_range.clone());
+                }
+                MaxSubarray {
+                    range: a.range.start..b.range.end,
+                    total: t_add(&a.total, &b.total),
+                    prefix,
+                    prefix_end,
+                    suffix,
+                    suffix_start,
+                    best,
+                    best_range,
+                }
+            },
+            {
+                let t_min_exists = Rc::new(t_min_exists);
+                move || MaxSubarray {
+                    range: 0..0,
+                    total: t_zero(),
+                    prefix: t_min_exists(),
+                    prefix_end: 0,
+                    suffix: t_min_exists(),
+                    suffix_start: 0,
+                    best: t_min_exists(),
+                    best_range: 0..0,
+                }
+            },
+        )
+        .set_value_getter(|t, _| t.total)
+        .set_value_setter(leaf)
+    }
+}
+
+pub fn segment_tree_builder_max_subarray<T>(vec: Vec<T>) -> SegmentTreeMaxSubarrayBuilder<T>
+where
+    T: Clone,
+{
+    SegmentTreeMaxSubarrayBuilder::new(vec)
+}
+
+#[doc = include_str!("../doc_new_max_subarray.md")]
+pub fn segment_tree_new_max_subarray<T>(
+    vec: Vec<T>,
+) -> seg_type!(T = MaxSubarray<T>, TFolded = T, TGetter = T, TSetter = T)
+where
+    T: CommutativeRingOrd + MinExists,
+{
+    segment_tree_builder_max_subarray(vec)
+        .set_all_auto()
+        .build()
+}
+
+#[doc = include_str!("../doc_new_max_subarray_with_boundaries.md")]
+pub fn segment_tree_new_max_subarray_with_boundaries<T>(
+    vec: Vec<T>,
+) -> seg_type!(
+       T = MaxSubarray<T>,
+       TFolded = MaxSubarray<T>,
+       TGetter = T,
+       TSetter = T,
+   )
+where
+    T: CommutativeRingOrd + MinExists,
+{
+    segment_tree_builder_max_subarray(vec)
+        .set_all_auto()
+        .build_with_boundaries()
+}
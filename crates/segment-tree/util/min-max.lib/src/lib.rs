@@ -1,6 +1,6 @@
 use max_exists::MaxExists;
 use min_exists::MinExists;
-use segment_tree::{segment_tree_new, SegmentTree};
+use segment_tree::{segment_tree_new, segment_tree_new_static, SegmentTree, SegmentTreeStatic};
 use segment_tree_util_type::seg_type;
 use std::cmp;
 
@@ -113,14 +113,18 @@ macro_rules! f {
         }
 
         #[doc = include_str!($doc_fn_new)]
-        pub fn $fn_new<T>(vec: Vec<T>) -> seg_type!(T = T)
+        pub fn $fn_new<T>(vec: Vec<T>) -> SegmentTreeStatic<T>
         where
             T: Clone + cmp::PartialOrd + $max_or_min_exists,
         {
-            let b = $fn_builder(vec)
-                .set_ord_by_partial_ord()
-                .$set_max_or_min_exists_auto();
-            unsafe { b.build_unchecked() }
+            fn op<T: Clone + cmp::PartialOrd>(a: &T, b: &T) -> T {
+                match a.partial_cmp(b) {
+                    Some(cmp::Ordering::$less_or_greater | cmp::Ordering::Equal) => a.clone(),
+                    Some(cmp::Ordering::$greater_or_less) => b.clone(),
+                    None => panic!("{}: partial_cmp should be total", stringify!($builder_name)),
+                }
+            }
+            segment_tree_new_static(vec, op::<T>, T::$max_or_min_exists_method)
         }
     };
 }
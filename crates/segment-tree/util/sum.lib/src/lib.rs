@@ -1,6 +1,6 @@
 use commutative_ring::CommutativeRing;
 use monoid::Monoid;
-use segment_tree::{segment_tree_new, SegmentTree};
+use segment_tree::{segment_tree_new, segment_tree_new_static, SegmentTree, SegmentTreeStatic};
 use segment_tree_util_type::seg_type;
 use std::ops;
 
@@ -83,10 +83,9 @@ pub fn segment_tree_builder_sum<T>(vec: Vec<T>) -> SegmentTreeSumBuilder<T> {
 }
 
 #[doc = include_str!("../doc_new_sum.md")]
-pub fn segment_tree_new_sum<T>(vec: Vec<T>) -> seg_type!(T = T)
+pub fn segment_tree_new_sum<T>(vec: Vec<T>) -> SegmentTreeStatic<T>
 where
     T: CommutativeRing,
 {
-    let builder = segment_tree_builder_sum(vec).set_add_zero_by_commutative_ring_add();
-    unsafe { builder.build_unchecked() }
+    segment_tree_new_static(vec, T::add, T::zero)
 }
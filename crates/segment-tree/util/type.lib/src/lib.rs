@@ -1,3 +1,7 @@
+/// # ユーティリティのビルダーが返す型
+///
+/// 関数を `impl Fn` として持つので、構造体のフィールドなどに書くことはできない。
+/// 名前を付けて保持したい場合は `into_dyn()` で `SegmentTreeDyn` に変換する。
 #[macro_export]
 macro_rules! seg_type {
     (T $($tt:tt)*) => {
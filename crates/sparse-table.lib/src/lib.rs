@@ -0,0 +1,176 @@
+use access_range::IntoAccessRange;
+use ceil_log2::ceil_log2_usize;
+use monoid::{Monoid, QuickMonoid};
+
+/// # Sparse Table
+///
+/// 冪等なモノイド (`op(a, a) == a`) について、 $O(1)$ で区間の畳み込みを行う。
+/// 値の更新はできない。
+///
+/// `MinMonoid` 、 `MaxMonoid` や最大公約数などが冪等なモノイドの例である。
+pub struct SparseTable<T, TFolded, TIntoFolded, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+    TIntoFolded: Fn(T) -> TFolded,
+{
+    /// table[k][i] は i..i + 2^k の畳み込み
+    table: Vec<Vec<T>>,
+    monoid: QuickMonoid<T, Op, Id>,
+    t_into_folded: TIntoFolded,
+}
+
+/// # 名前を付けられる Sparse Table の型
+///
+/// 関数をすべて `fn` ポインタとして持つ `SparseTable` 。
+pub type SparseTableStatic<T, TFolded = T> =
+    SparseTable<T, TFolded, fn(T) -> TFolded, fn(&T, &T) -> T, fn() -> T>;
+
+impl<T, TFolded, TIntoFolded, Op, Id> SparseTable<T, TFolded, TIntoFolded, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+    TIntoFolded: Fn(T) -> TFolded,
+{
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.table.first().map_or(0, |v| v.len())
+    }
+
+    #[inline]
+    pub fn set_value_folded<TFolded2, TIntoFolded2>(
+        self,
+        value_folded: TIntoFolded2,
+    ) -> SparseTable<T, TFolded2, TIntoFolded2, Op, Id>
+    where
+        TIntoFolded2: Fn(T) -> TFolded2,
+    {
+        SparseTable {
+            table: self.table,
+            monoid: self.monoid,
+            t_into_folded: value_folded,
+        }
+    }
+
+    /// # 区間の畳み込み
+    ///
+    /// ## 計算量
+    ///
+    /// $O(1)$
+    pub fn fold(&self, range: impl IntoAccessRange<usize>) -> TFolded {
+        let range = range.into_access_range().into_range(self.size());
+        (self.t_into_folded)({
+            if range.start >= range.end {
+                self.monoid.id()
+            } else {
+                // floor(log2(len))
+                let k = ceil_log2_usize(range.end - range.start + 1) - 1;
+                let row = &self.table[k];
+                self.monoid
+                    .op(&row[range.start], &row[range.end - (1 << k)])
+            }
+        })
+    }
+}
+
+/// # Sparse Table の構築 (`QuickMonoid` による指定)
+///
+/// ## 計算量
+///
+/// $O(N \log N)$
+pub fn sparse_table_new_quick_monoid<T, Op, Id>(
+    vec: Vec<T>,
+    monoid: QuickMonoid<T, Op, Id>,
+) -> SparseTable<T, T, fn(T) -> T, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+{
+    fn id_fn<T>(x: T) -> T {
+        x
+    }
+
+    let n = vec.len();
+    // 2^(levels - 1) <= n < 2^levels
+    let levels = ceil_log2_usize(n + 1);
+    let mut table = Vec::with_capacity(levels);
+    table.push(vec);
+    for k in 1..levels {
+        let half = 1 << (k - 1);
+        let prev: &Vec<T> = &table[k - 1];
+        let row = (0..=n - (1 << k))
+            .map(|i| monoid.op(&prev[i], &prev[i + half]))
+            .collect();
+        table.push(row);
+    }
+    SparseTable {
+        table,
+        monoid,
+        t_into_folded: id_fn as fn(T) -> T,
+    }
+}
+
+/// # Sparse Table の構築 (直接指定)
+///
+/// `op` は冪等でなければならない。
+///
+/// ## 計算量
+///
+/// $O(N \log N)$
+///
+/// ## 例
+/// ```
+/// use sparse_table::sparse_table_new;
+/// let st = sparse_table_new(vec![3, 1, 4, 1, 5, 9, 2, 6], |a: &i32, b: &i32| *a.max(b), || i32::MIN);
+/// assert_eq!(st.fold(..), 9);
+/// assert_eq!(st.fold(..4), 4);
+/// assert_eq!(st.fold(6..), 6);
+/// assert_eq!(st.fold(3..3), i32::MIN);
+/// ```
+#[inline]
+pub fn sparse_table_new<T, Op, Id>(
+    vec: Vec<T>,
+    op: Op,
+    id: Id,
+) -> SparseTable<T, T, fn(T) -> T, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+{
+    sparse_table_new_quick_monoid(vec, QuickMonoid::new(op, id))
+}
+
+/// # Sparse Table の構築 (`Monoid` による指定)
+///
+/// モノイドは冪等でなければならない。
+///
+/// ## 計算量
+///
+/// $O(N \log N)$
+///
+/// ## 例
+/// ```
+/// use min_monoid::MinMonoid;
+/// use sparse_table::sparse_table_new_monoid;
+/// let st = sparse_table_new_monoid(
+///     vec![3, 1, 4, 1, 5, 9, 2, 6].into_iter().map(MinMonoid).collect(),
+/// )
+/// .set_value_folded(|x| x.0);
+/// assert_eq!(st.fold(..), 1);
+/// assert_eq!(st.fold(4..), 2);
+/// assert_eq!(st.fold(4..6), 5);
+/// ```
+#[inline]
+pub fn sparse_table_new_monoid<T>(vec: Vec<T>) -> SparseTableStatic<T>
+where
+    T: Clone + Monoid,
+{
+    sparse_table_new_quick_monoid(vec, T::as_quick())
+}
+
+#[cfg(test)]
+mod test;
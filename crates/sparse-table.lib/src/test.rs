@@ -0,0 +1,37 @@
+use super::*;
+use max_monoid::MaxMonoid;
+use rand::{self, Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+#[test]
+fn test_min_random() {
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    for n in 0..=70 {
+        let v = (0..n)
+            .map(|_| rng.gen_range(-1000..1000))
+            .collect::<Vec<i64>>();
+        let st = sparse_table_new(v.clone(), |a: &i64, b: &i64| *a.min(b), || i64::MAX);
+        assert_eq!(st.size(), n);
+        for l in 0..=n {
+            for r in l..=n {
+                assert_eq!(
+                    st.fold(l..r),
+                    v[l..r].iter().copied().min().unwrap_or(i64::MAX)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_static_monoid() {
+    struct S {
+        st: SparseTableStatic<MaxMonoid<i32>>,
+    }
+    let s = S {
+        st: sparse_table_new_monoid(vec![MaxMonoid(2), MaxMonoid(7), MaxMonoid(1)]),
+    };
+    assert_eq!(s.st.fold(..), MaxMonoid(7));
+    assert_eq!(s.st.fold(2..), MaxMonoid(1));
+    assert_eq!(s.st.fold(..0), MaxMonoid(i32::MIN));
+}
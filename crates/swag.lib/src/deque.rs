@@ -0,0 +1,200 @@
+use monoid::{Monoid, QuickMonoid};
+
+/// # 畳み込み可能な両端キュー
+///
+/// 2 つのスタックで管理し、片方が空のときに取り出す場合は、
+/// もう片方を半分に分けて積み直す。
+pub struct FoldableDeque<T, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+{
+    /// 末尾が先頭。 (値, その値から先頭までの畳み込み)
+    front: Vec<(T, T)>,
+    /// 末尾が末尾。 (値, 末尾側のスタックの底からその値までの畳み込み)
+    back: Vec<(T, T)>,
+    monoid: QuickMonoid<T, Op, Id>,
+}
+
+/// # 名前を付けられる畳み込み可能な両端キューの型
+pub type FoldableDequeStatic<T> = FoldableDeque<T, fn(&T, &T) -> T, fn() -> T>;
+
+impl<T, Op, Id> FoldableDeque<T, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+{
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// # 先頭への追加
+    ///
+    /// ## 計算量
+    ///
+    /// $O(1)$
+    pub fn push_front(&mut self, x: T) {
+        Self::push_front_to(&self.monoid, &mut self.front, x);
+    }
+
+    /// # 末尾への追加
+    ///
+    /// ## 計算量
+    ///
+    /// $O(1)$
+    pub fn push_back(&mut self, x: T) {
+        Self::push_back_to(&self.monoid, &mut self.back, x);
+    }
+
+    /// # 先頭の取り出し
+    ///
+    /// ## 計算量
+    ///
+    /// 償却 $O(1)$
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.front.is_empty() {
+            self.rebalance(self.len() - self.len() / 2);
+        }
+        self.front.pop().map(|(x, _)| x)
+    }
+
+    /// # 末尾の取り出し
+    ///
+    /// ## 計算量
+    ///
+    /// 償却 $O(1)$
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.back.is_empty() {
+            self.rebalance(self.len() / 2);
+        }
+        self.back.pop().map(|(x, _)| x)
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.front
+            .last()
+            .or_else(|| self.back.first())
+            .map(|(x, _)| x)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.back
+            .last()
+            .or_else(|| self.front.first())
+            .map(|(x, _)| x)
+    }
+
+    /// # 全体の畳み込み
+    ///
+    /// ## 計算量
+    ///
+    /// $O(1)$
+    pub fn fold_all(&self) -> T {
+        match (self.front.last(), self.back.last()) {
+            (Some((_, a)), Some((_, b))) => self.monoid.op(a, b),
+            (Some((_, a)), None) => a.clone(),
+            (None, Some((_, b))) => b.clone(),
+            (None, None) => self.monoid.id(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.front.clear();
+        self.back.clear();
+    }
+
+    fn push_front_to(monoid: &QuickMonoid<T, Op, Id>, front: &mut Vec<(T, T)>, x: T) {
+        let folded = match front.last() {
+            Some((_, folded)) => monoid.op(&x, folded),
+            None => x.clone(),
+        };
+        front.push((x, folded));
+    }
+
+    fn push_back_to(monoid: &QuickMonoid<T, Op, Id>, back: &mut Vec<(T, T)>, x: T) {
+        let folded = match back.last() {
+            Some((_, folded)) => monoid.op(folded, &x),
+            None => x.clone(),
+        };
+        back.push((x, folded));
+    }
+
+    /// 全体を先頭から順に並べ、先頭の `front_len` 個を前側、残りを後側に積み直す
+    fn rebalance(&mut self, front_len: usize) {
+        let mut all = self
+            .front
+            .drain(..)
+            .rev()
+            .chain(self.back.drain(..))
+            .map(|(x, _)| x)
+            .collect::<Vec<_>>();
+        let back = all.split_off(front_len);
+        for x in all.into_iter().rev() {
+            Self::push_front_to(&self.monoid, &mut self.front, x);
+        }
+        for x in back {
+            Self::push_back_to(&self.monoid, &mut self.back, x);
+        }
+    }
+}
+
+/// # 畳み込み可能な両端キューの構築 (`QuickMonoid` による指定)
+pub fn foldable_deque_new_quick_monoid<T, Op, Id>(
+    monoid: QuickMonoid<T, Op, Id>,
+) -> FoldableDeque<T, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+{
+    FoldableDeque {
+        front: Vec::new(),
+        back: Vec::new(),
+        monoid,
+    }
+}
+
+/// # 畳み込み可能な両端キューの構築 (直接指定)
+///
+/// ## 例
+/// ```
+/// use swag::foldable_deque_new;
+/// // 文字列の連結 (非可換)
+/// let mut dq = foldable_deque_new(|a: &String, b: &String| a.clone() + b, String::new);
+/// dq.push_back("b".to_string());
+/// dq.push_front("a".to_string());
+/// dq.push_back("c".to_string());
+/// assert_eq!(dq.fold_all(), "abc");
+/// assert_eq!(dq.pop_back(), Some("c".to_string()));
+/// assert_eq!(dq.fold_all(), "ab");
+/// assert_eq!(dq.pop_back(), Some("b".to_string()));
+/// assert_eq!(dq.pop_back(), Some("a".to_string()));
+/// assert_eq!(dq.pop_front(), None);
+/// assert_eq!(dq.fold_all(), "");
+/// ```
+#[inline]
+pub fn foldable_deque_new<T, Op, Id>(op: Op, id: Id) -> FoldableDeque<T, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+{
+    foldable_deque_new_quick_monoid(QuickMonoid::new(op, id))
+}
+
+/// # 畳み込み可能な両端キューの構築 (`Monoid` による指定)
+#[inline]
+pub fn foldable_deque_new_monoid<T>() -> FoldableDequeStatic<T>
+where
+    T: Clone + Monoid,
+{
+    foldable_deque_new_quick_monoid(T::as_quick())
+}
@@ -0,0 +1,13 @@
+//! # Sliding Window Aggregation
+//!
+//! モノイドの値を持つキュー、両端キューで、全体の畳み込みを償却 $O(1)$ で求める。
+//! 可換性や逆元は必要ない。
+
+mod deque;
+mod queue;
+
+pub use deque::*;
+pub use queue::*;
+
+#[cfg(test)]
+mod test;
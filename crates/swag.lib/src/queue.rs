@@ -0,0 +1,157 @@
+use monoid::{Monoid, QuickMonoid};
+
+/// # 畳み込み可能なキュー
+///
+/// 2 つのスタックで管理する。
+/// 前側のスタックは各要素からキューの先頭までの畳み込みを持ち、
+/// 後側のスタックは全体の畳み込みのみを持つ。
+pub struct FoldableQueue<T, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+{
+    /// 末尾がキューの先頭。 (値, その値から先頭までの畳み込み)
+    front: Vec<(T, T)>,
+    /// 末尾がキューの末尾
+    back: Vec<T>,
+    back_folded: T,
+    monoid: QuickMonoid<T, Op, Id>,
+}
+
+/// # 名前を付けられる畳み込み可能なキューの型
+pub type FoldableQueueStatic<T> = FoldableQueue<T, fn(&T, &T) -> T, fn() -> T>;
+
+impl<T, Op, Id> FoldableQueue<T, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+{
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// # 末尾への追加
+    ///
+    /// ## 計算量
+    ///
+    /// $O(1)$
+    pub fn push(&mut self, x: T) {
+        self.back_folded = self.monoid.op(&self.back_folded, &x);
+        self.back.push(x);
+    }
+
+    /// # 先頭の取り出し
+    ///
+    /// ## 計算量
+    ///
+    /// 償却 $O(1)$
+    pub fn pop(&mut self) -> Option<T> {
+        if self.front.is_empty() {
+            self.move_back_to_front();
+        }
+        self.front.pop().map(|(x, _)| x)
+    }
+
+    /// # 先頭の参照
+    ///
+    /// ## 計算量
+    ///
+    /// $O(1)$
+    pub fn front(&self) -> Option<&T> {
+        self.front.last().map(|(x, _)| x).or_else(|| self.back.first())
+    }
+
+    /// # 全体の畳み込み
+    ///
+    /// ## 計算量
+    ///
+    /// $O(1)$
+    pub fn fold_all(&self) -> T {
+        match self.front.last() {
+            Some((_, folded)) => self.monoid.op(folded, &self.back_folded),
+            None => self.back_folded.clone(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.front.clear();
+        self.back.clear();
+        self.back_folded = self.monoid.id();
+    }
+
+    fn move_back_to_front(&mut self) {
+        debug_assert!(self.front.is_empty());
+        while let Some(x) = self.back.pop() {
+            let folded = match self.front.last() {
+                Some((_, folded)) => self.monoid.op(&x, folded),
+                None => x.clone(),
+            };
+            self.front.push((x, folded));
+        }
+        self.back_folded = self.monoid.id();
+    }
+}
+
+/// # 畳み込み可能なキューの構築 (`QuickMonoid` による指定)
+pub fn foldable_queue_new_quick_monoid<T, Op, Id>(
+    monoid: QuickMonoid<T, Op, Id>,
+) -> FoldableQueue<T, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+{
+    FoldableQueue {
+        front: Vec::new(),
+        back: Vec::new(),
+        back_folded: monoid.id(),
+        monoid,
+    }
+}
+
+/// # 畳み込み可能なキューの構築 (直接指定)
+///
+/// ## 例
+/// ```
+/// use swag::foldable_queue_new;
+/// // 長さ 3 の窓の最小値
+/// let v = [3, 1, 4, 1, 5, 9, 2, 6];
+/// let mut q = foldable_queue_new(|a: &i32, b: &i32| *a.min(b), || i32::MAX);
+/// let mut res = vec![];
+/// for (i, &x) in v.iter().enumerate() {
+///     q.push(x);
+///     if i >= 3 {
+///         q.pop();
+///     }
+///     if i >= 2 {
+///         res.push(q.fold_all());
+///     }
+/// }
+/// assert_eq!(res, vec![1, 1, 1, 1, 2, 2]);
+/// ```
+#[inline]
+pub fn foldable_queue_new<T, Op, Id>(op: Op, id: Id) -> FoldableQueue<T, Op, Id>
+where
+    T: Clone,
+    Op: Fn(&T, &T) -> T,
+    Id: Fn() -> T,
+{
+    foldable_queue_new_quick_monoid(QuickMonoid::new(op, id))
+}
+
+/// # 畳み込み可能なキューの構築 (`Monoid` による指定)
+#[inline]
+pub fn foldable_queue_new_monoid<T>() -> FoldableQueueStatic<T>
+where
+    T: Clone + Monoid,
+{
+    foldable_queue_new_quick_monoid(T::as_quick())
+}
@@ -0,0 +1,77 @@
+use crate::{foldable_deque_new, foldable_queue_new, foldable_queue_new_monoid};
+use min_monoid::MinMonoid;
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+use std::collections::VecDeque;
+
+const MOD: i64 = 998244353;
+
+/// x ↦ a x + b の合成 (先に左を適用する)
+fn affine_op(&(a1, b1): &(i64, i64), &(a2, b2): &(i64, i64)) -> (i64, i64) {
+    (a1 * a2 % MOD, (b1 * a2 + b2) % MOD)
+}
+
+fn naive_fold(v: &VecDeque<(i64, i64)>) -> (i64, i64) {
+    v.iter().fold((1, 0), |acc, x| affine_op(&acc, x))
+}
+
+#[test]
+fn test_queue_affine_random() {
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    let mut q = foldable_queue_new(affine_op, || (1, 0));
+    let mut naive = VecDeque::new();
+    for _ in 0..1000 {
+        if rng.gen_bool(0.6) {
+            let x = (rng.gen_range(0..MOD), rng.gen_range(0..MOD));
+            q.push(x);
+            naive.push_back(x);
+        } else {
+            assert_eq!(q.pop(), naive.pop_front());
+        }
+        assert_eq!(q.len(), naive.len());
+        assert_eq!(q.front().copied(), naive.front().copied());
+        assert_eq!(q.fold_all(), naive_fold(&naive));
+    }
+}
+
+#[test]
+fn test_deque_affine_random() {
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    let mut dq = foldable_deque_new(affine_op, || (1, 0));
+    let mut naive = VecDeque::new();
+    for _ in 0..1000 {
+        let x = (rng.gen_range(0..MOD), rng.gen_range(0..MOD));
+        match rng.gen_range(0..4) {
+            0 => {
+                dq.push_front(x);
+                naive.push_front(x);
+            }
+            1 => {
+                dq.push_back(x);
+                naive.push_back(x);
+            }
+            2 => assert_eq!(dq.pop_front(), naive.pop_front()),
+            _ => assert_eq!(dq.pop_back(), naive.pop_back()),
+        }
+        assert_eq!(dq.len(), naive.len());
+        assert_eq!(dq.front().copied(), naive.front().copied());
+        assert_eq!(dq.back().copied(), naive.back().copied());
+        assert_eq!(dq.fold_all(), naive_fold(&naive));
+    }
+}
+
+#[test]
+fn test_queue_monoid() {
+    let mut q = foldable_queue_new_monoid::<MinMonoid<i32>>();
+    assert_eq!(q.fold_all(), MinMonoid(i32::MAX));
+    q.push(MinMonoid(3));
+    q.push(MinMonoid(1));
+    q.push(MinMonoid(2));
+    assert_eq!(q.fold_all(), MinMonoid(1));
+    q.pop();
+    q.pop();
+    assert_eq!(q.fold_all(), MinMonoid(2));
+    q.clear();
+    assert!(q.is_empty());
+    assert_eq!(q.fold_all(), MinMonoid(i32::MAX));
+}
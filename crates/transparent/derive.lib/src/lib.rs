@@ -20,14 +20,8 @@ fn generate_getter(derive_input: &DeriveInput) -> Result<TokenStream, syn::Error
         ));
         match &derive_input.data {
             syn::Data::Struct(v) => match &v.fields {
-                syn::Fields::Unnamed(fields) => {
-                    if fields.unnamed.len() == 1 {
-                        &fields.unnamed[0]
-                    } else {
-                        // TODO: support single field named struct
-                        return err;
-                    }
-                }
+                syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0],
+                // TODO: support single field named struct
                 _ => return err,
             },
             _ => return err,
@@ -0,0 +1,42 @@
+/// 完備辞書 (rank のみ)
+pub(crate) struct BitVector {
+    blocks: Vec<u64>,
+    /// ranks[i] は blocks[..i] の 1 の個数
+    ranks: Vec<usize>,
+}
+
+impl BitVector {
+    pub(crate) fn new(bits: impl ExactSizeIterator<Item = bool>) -> Self {
+        let mut blocks = vec![0_u64; bits.len() / 64 + 1];
+        for (i, b) in bits.enumerate() {
+            if b {
+                blocks[i / 64] |= 1 << (i % 64);
+            }
+        }
+        let mut ranks = Vec::with_capacity(blocks.len());
+        let mut acc = 0;
+        for block in &blocks {
+            ranks.push(acc);
+            acc += block.count_ones() as usize;
+        }
+        Self { blocks, ranks }
+    }
+
+    #[inline]
+    pub(crate) fn get(&self, i: usize) -> bool {
+        self.blocks[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    /// [0, i) の 1 の個数
+    #[inline]
+    pub(crate) fn rank1(&self, i: usize) -> usize {
+        let mask = (1_u64 << (i % 64)) - 1;
+        self.ranks[i / 64] + (self.blocks[i / 64] & mask).count_ones() as usize
+    }
+
+    /// [0, i) の 0 の個数
+    #[inline]
+    pub(crate) fn rank0(&self, i: usize) -> usize {
+        i - self.rank1(i)
+    }
+}
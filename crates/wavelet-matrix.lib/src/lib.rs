@@ -0,0 +1,284 @@
+use access_range::IntoAccessRange;
+use bit_vector::BitVector;
+use int::Int;
+use shrink::{shrink, Shrink};
+use std::ops::{Bound, RangeBounds};
+use std::rc::Rc;
+
+mod bit_vector;
+
+/// # Wavelet Matrix
+///
+/// 値を `shrink` で座標圧縮して持ち、区間に対する順序統計の問い合わせに
+/// $O(\log \sigma)$ で答える。 $\sigma$ は異なる値の個数である。
+/// 値の更新はできない。
+pub struct WaveletMatrix<Index: Int> {
+    /// levels[k] は上から k 番目のビット
+    levels: Vec<BitVector>,
+    /// zeros[k] は levels[k] の 0 の個数
+    zeros: Vec<usize>,
+    size: usize,
+    shrink: Rc<Shrink<Index::UnsignedIntSameSize, Index>>,
+}
+
+impl<Index: Int> WaveletMatrix<Index> {
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    #[inline]
+    pub fn shrink(&self) -> &Rc<Shrink<Index::UnsignedIntSameSize, Index>> {
+        &self.shrink
+    }
+
+    #[inline]
+    fn height(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// 値が `x` 以上である最小の圧縮後の値
+    fn lower_code(&self, x: Index) -> usize {
+        let len = self.shrink.shrinked_len();
+        if len == 0 || x <= self.shrink.shrinkable_min() {
+            0
+        } else if x > self.shrink.shrinkable_max() {
+            len
+        } else {
+            self.shrink.shrink_index(x)
+        }
+    }
+
+    /// 値が `x` より大きい最小の圧縮後の値
+    fn upper_code(&self, x: Index) -> usize {
+        let len = self.shrink.shrinked_len();
+        if len == 0 || x >= self.shrink.shrinkable_max() {
+            len
+        } else {
+            self.lower_code(x + Index::one())
+        }
+    }
+
+    fn code_range(&self, value_range: impl RangeBounds<Index>) -> (usize, usize) {
+        let from = match value_range.start_bound() {
+            Bound::Included(&x) => self.lower_code(x),
+            Bound::Excluded(&x) => self.upper_code(x),
+            Bound::Unbounded => 0,
+        };
+        let to = match value_range.end_bound() {
+            Bound::Included(&x) => self.upper_code(x),
+            Bound::Excluded(&x) => self.lower_code(x),
+            Bound::Unbounded => self.shrink.shrinked_len(),
+        };
+        (from, to)
+    }
+
+    #[inline]
+    fn decode(&self, code: usize) -> Index {
+        *self.shrink.unshrink(code).unwrap_range_inclusive().end()
+    }
+
+    /// # 取得
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log \sigma)$
+    pub fn get(&self, mut index: usize) -> Index {
+        assert!(index < self.size, "index out of range: {}", index);
+        let mut code = 0;
+        for (k, level) in self.levels.iter().enumerate() {
+            code <<= 1;
+            if level.get(index) {
+                code |= 1;
+                index = self.zeros[k] + level.rank1(index);
+            } else {
+                index = level.rank0(index);
+            }
+        }
+        self.decode(code)
+    }
+
+    /// 区間内で、圧縮後の値が `code` 未満であるものの個数
+    fn count_less_code(&self, l: usize, r: usize, code: usize) -> usize {
+        if code >= 1 << self.height() {
+            return r - l;
+        }
+        let (mut l, mut r) = (l, r);
+        let mut res = 0;
+        for (k, level) in self.levels.iter().enumerate() {
+            let bit = code >> (self.height() - 1 - k) & 1;
+            if bit == 1 {
+                res += level.rank0(r) - level.rank0(l);
+                l = self.zeros[k] + level.rank1(l);
+                r = self.zeros[k] + level.rank1(r);
+            } else {
+                l = level.rank0(l);
+                r = level.rank0(r);
+            }
+        }
+        res
+    }
+
+    /// # 区間で k 番目 (0-indexed) に小さい値
+    ///
+    /// 区間の長さが `k` 以下であれば `None` を返す。
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log \sigma)$
+    pub fn kth_smallest(&self, range: impl IntoAccessRange<usize>, k: usize) -> Option<Index> {
+        let range = range.into_access_range().into_range(self.size);
+        if range.start >= range.end || k >= range.end - range.start {
+            return None;
+        }
+        let (mut l, mut r) = (range.start, range.end);
+        let mut k = k;
+        let mut code = 0;
+        for (i, level) in self.levels.iter().enumerate() {
+            code <<= 1;
+            let zeros = level.rank0(r) - level.rank0(l);
+            if k < zeros {
+                l = level.rank0(l);
+                r = level.rank0(r);
+            } else {
+                k -= zeros;
+                code |= 1;
+                l = self.zeros[i] + level.rank1(l);
+                r = self.zeros[i] + level.rank1(r);
+            }
+        }
+        Some(self.decode(code))
+    }
+
+    /// # 区間で k 番目 (0-indexed) に大きい値
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log \sigma)$
+    pub fn kth_largest(&self, range: impl IntoAccessRange<usize>, k: usize) -> Option<Index> {
+        let range = range.into_access_range().into_range(self.size);
+        let len = range.end.saturating_sub(range.start);
+        if k >= len {
+            return None;
+        }
+        self.kth_smallest(range, len - 1 - k)
+    }
+
+    /// # 区間に含まれる `value` の個数
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log \sigma)$
+    pub fn rank(&self, value: Index, range: impl IntoAccessRange<usize>) -> usize {
+        self.range_freq(range, value..=value)
+    }
+
+    /// # 区間に含まれる、値が `value_range` に含まれるものの個数
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log \sigma)$
+    pub fn range_freq(
+        &self,
+        range: impl IntoAccessRange<usize>,
+        value_range: impl RangeBounds<Index>,
+    ) -> usize {
+        let range = range.into_access_range().into_range(self.size);
+        if range.start >= range.end {
+            return 0;
+        }
+        let (from, to) = self.code_range(value_range);
+        if from >= to {
+            return 0;
+        }
+        self.count_less_code(range.start, range.end, to)
+            - self.count_less_code(range.start, range.end, from)
+    }
+
+    /// # 区間に含まれる、 `upper` 未満の最大の値
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log \sigma)$
+    pub fn prev_value(&self, range: impl IntoAccessRange<usize>, upper: Index) -> Option<Index> {
+        let range = range.into_access_range().into_range(self.size);
+        if range.start >= range.end {
+            return None;
+        }
+        let count = self.count_less_code(range.start, range.end, self.lower_code(upper));
+        if count == 0 {
+            None
+        } else {
+            self.kth_smallest(range, count - 1)
+        }
+    }
+
+    /// # 区間に含まれる、 `lower` 以上の最小の値
+    ///
+    /// ## 計算量
+    ///
+    /// $O(\log \sigma)$
+    pub fn next_value(&self, range: impl IntoAccessRange<usize>, lower: Index) -> Option<Index> {
+        let range = range.into_access_range().into_range(self.size);
+        if range.start >= range.end {
+            return None;
+        }
+        let count = self.count_less_code(range.start, range.end, self.lower_code(lower));
+        self.kth_smallest(range, count)
+    }
+}
+
+/// # Wavelet Matrix の構築
+///
+/// ## 計算量
+///
+/// $O(N \log N)$
+///
+/// ## 例
+///
+/// ```
+/// use wavelet_matrix::wavelet_matrix_new;
+/// let wm = wavelet_matrix_new(vec![5_i64, -3, 8, 5, 100, 2, -3]);
+/// assert_eq!(wm.kth_smallest(.., 0), Some(-3));
+/// assert_eq!(wm.kth_smallest(1..5, 2), Some(8));
+/// assert_eq!(wm.kth_smallest(1..5, 4), None);
+/// assert_eq!(wm.kth_largest(.., 0), Some(100));
+/// assert_eq!(wm.rank(5, ..), 2);
+/// assert_eq!(wm.rank(6, ..), 0);
+/// assert_eq!(wm.range_freq(.., 0..=8), 4);
+/// assert_eq!(wm.range_freq(2.., ..5), 2);
+/// assert_eq!(wm.prev_value(.., 5), Some(2));
+/// assert_eq!(wm.prev_value(..2, -3), None);
+/// assert_eq!(wm.next_value(.., 6), Some(8));
+/// assert_eq!(wm.next_value(..4, 9), None);
+/// assert_eq!(wm.get(4), 100);
+/// ```
+pub fn wavelet_matrix_new<Index: Int>(vec: Vec<Index>) -> WaveletMatrix<Index> {
+    let shrink: Rc<Shrink<Index::UnsignedIntSameSize, Index>> = shrink(vec.clone());
+    let size = vec.len();
+    let mut codes: Vec<usize> = vec.into_iter().map(|x| shrink.shrink_index(x)).collect();
+    let mut height = 0;
+    while (1 << height) < shrink.shrinked_len() {
+        height += 1;
+    }
+    let mut levels = Vec::with_capacity(height);
+    let mut zeros = Vec::with_capacity(height);
+    for k in (0..height).rev() {
+        let level = BitVector::new(codes.iter().map(|&c| c >> k & 1 == 1));
+        let (mut next, ones): (Vec<usize>, Vec<usize>) =
+            codes.iter().partition(|&&c| c >> k & 1 == 0);
+        zeros.push(next.len());
+        next.extend(ones);
+        codes = next;
+        levels.push(level);
+    }
+    WaveletMatrix {
+        levels,
+        zeros,
+        size,
+        shrink,
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,70 @@
+use crate::wavelet_matrix_new;
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+#[test]
+fn test_empty() {
+    let wm = wavelet_matrix_new(Vec::<i32>::new());
+    assert_eq!(wm.size(), 0);
+    assert_eq!(wm.kth_smallest(.., 0), None);
+    assert_eq!(wm.range_freq(.., ..), 0);
+    assert_eq!(wm.prev_value(.., 0), None);
+    assert_eq!(wm.next_value(.., 0), None);
+}
+
+#[test]
+fn test_extreme_values() {
+    let wm = wavelet_matrix_new(vec![i64::MIN, i64::MAX, 0, i64::MAX]);
+    assert_eq!(wm.rank(i64::MAX, ..), 2);
+    assert_eq!(wm.range_freq(.., ..=i64::MAX), 4);
+    assert_eq!(wm.range_freq(.., i64::MIN..i64::MIN), 0);
+    assert_eq!(
+        wm.range_freq(
+            ..,
+            (
+                std::ops::Bound::Excluded(i64::MAX),
+                std::ops::Bound::Unbounded
+            )
+        ),
+        0
+    );
+    assert_eq!(wm.prev_value(.., i64::MIN), None);
+    assert_eq!(wm.next_value(.., i64::MIN), Some(i64::MIN));
+    assert_eq!(wm.kth_smallest(.., 3), Some(i64::MAX));
+}
+
+#[test]
+fn test_random() {
+    let mut rng = Xoshiro256PlusPlus::from_seed([0; 32]);
+    for n in 1..=30 {
+        let v = (0..n).map(|_| rng.gen_range(-10..10)).collect::<Vec<i32>>();
+        let wm = wavelet_matrix_new(v.clone());
+        for (i, &x) in v.iter().enumerate() {
+            assert_eq!(wm.get(i), x);
+        }
+        for _ in 0..100 {
+            let l = rng.gen_range(0..=n);
+            let r = rng.gen_range(l..=n);
+            let mut s = v[l..r].to_vec();
+            s.sort();
+            let k = rng.gen_range(0..=n);
+            assert_eq!(wm.kth_smallest(l..r, k), s.get(k).copied());
+            let a = rng.gen_range(-12..12);
+            let b = rng.gen_range(-12..12);
+            assert_eq!(wm.rank(a, l..r), s.iter().filter(|&&x| x == a).count());
+            assert_eq!(
+                wm.range_freq(l..r, a..b),
+                s.iter().filter(|&&x| a <= x && x < b).count()
+            );
+            assert_eq!(
+                wm.range_freq(l..r, a..=b),
+                s.iter().filter(|&&x| a <= x && x <= b).count()
+            );
+            assert_eq!(
+                wm.prev_value(l..r, a),
+                s.iter().rev().find(|&&x| x < a).copied()
+            );
+            assert_eq!(wm.next_value(l..r, a), s.iter().find(|&&x| x >= a).copied());
+        }
+    }
+}